@@ -1,7 +1,7 @@
 #![feature(portable_simd)]
 use std::{simd::{*, cmp::*}, ops::BitAnd, path::PathBuf};
 
-use benchmark::{util, realdata};
+use benchmark::{util, realdata, schema::RealFormat};
 use rand::{thread_rng, distributions::Uniform, Rng};
 use setops::{
     intersect::{
@@ -32,22 +32,22 @@ fn main() {
     let cli = Cli::parse();
 
     let real_datasets = [
-        "webdocs",
-        "twitter",
-        "as-skitter",
-        "census1881",
-        "census-income",
+        ("webdocs", RealFormat::Webdocs),
+        ("twitter", RealFormat::Snap),
+        ("as-skitter", RealFormat::Snap),
+        ("census1881", RealFormat::Roaring),
+        ("census-income", RealFormat::Roaring),
     ];
 
-    for real_dataset in real_datasets {
-        if let Err(s) = test_on_dataset(&cli, real_dataset) {
+    for (real_dataset, format) in real_datasets {
+        if let Err(s) = test_on_dataset(&cli, real_dataset, format) {
             eprintln!("error: {}", s);
         };
     }
 }
 
-fn test_on_dataset(cli: &Cli, real_dataset: &str) -> Result<(), String> {
-    let all_sets = realdata::load_sets(&cli.datasets, real_dataset)?;
+fn test_on_dataset(cli: &Cli, real_dataset: &str, format: RealFormat) -> Result<(), String> {
+    let all_sets = realdata::load_sets(&cli.datasets, real_dataset, format)?;
 
     let min_len = all_sets.iter().map(|s| s.len()).min().unwrap();
     let max_len = all_sets.iter().map(|s| s.len()).max().unwrap();
@@ -373,8 +373,8 @@ where
     LaneCount<LANES>: SupportedLaneCount,
     Simd<S, LANES>: BitAnd<Output=Simd<S, LANES>> + SimdPartialEq<Mask=Mask<S, LANES>>,
 {
-    let fesia_a = Fesia::<H, S, LANES>::from_sorted(set_a, hash_scale);
-    let fesia_b = Fesia::<H, S, LANES>::from_sorted(set_b, hash_scale);
+    let fesia_a = Fesia::<H, S, LANES>::from_sorted_scaled(set_a, hash_scale);
+    let fesia_b = Fesia::<H, S, LANES>::from_sorted_scaled(set_b, hash_scale);
 
     let mut writer = VecWriter::new();
 
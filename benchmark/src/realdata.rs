@@ -1,27 +1,36 @@
 use std::{
     path::PathBuf,
     fs::{File, self},
-    io::{BufReader, BufRead}
+    io::{BufReader, BufRead},
+    collections::BTreeMap,
 };
-use rand::{thread_rng, seq::SliceRandom};
+use rand::{thread_rng, seq::SliceRandom, Rng};
+use setops::intersect;
 use crate::{
     schema::*,
     datafile::{DatafileSet, self},
     fmt_open_err, path_str
 };
 
-const TEXT_FILE_EXT: &str = ".dat";
 const CACHE_EXT: &str = ".cache";
+const QUERY_STATS_FILE: &str = "queries.json";
+
+const MAX_SAMPLE_ATTEMPTS: usize = 1000;
+// Accepted deviation of the largest set from the target skew ratio.
+const SKEW_TOLERANCE: f64 = 1.25;
 
 pub fn generate_real_dataset(
     info: &RealDataset,
     root: &PathBuf,
     dataset_path: &PathBuf) -> Result<(), String>
 {
-    let sets = load_sets(root, &info.source)?;
+    let sets = load_sets(root, &info.source, info.format)?;
 
     println!("Building intersections...");
 
+    let rng = &mut thread_rng();
+    let mut stats = RealQueryStats::new();
+
     let _ = fs::remove_dir_all(&dataset_path);
     for count in info.set_count_start..=info.set_count_end {
         println!("  set count: {}", count);
@@ -34,15 +43,30 @@ pub fn generate_real_dataset(
                 e.to_string()
             ))?;
 
-        for i in 0..info.gen_count {
-            generate_real_intersection(&sets, &xdir, count as usize, i)?;
-        }
+        let count_stats = (0..info.gen_count)
+            .map(|i| generate_real_intersection(&sets, info, &xdir, count as usize, i, rng))
+            .collect::<Result<Vec<QueryStats>, String>>()?;
+
+        stats.insert(count, count_stats);
     }
 
+    let stats_path = dataset_path.join(QUERY_STATS_FILE);
+    let stats_file = File::create(&stats_path)
+        .map_err(|e| format!(
+            "failed to open file {}:\n{}",
+            path_str(&stats_path),
+            e.to_string()
+        ))?;
+
+    serde_json::to_writer(stats_file, &stats)
+        .map_err(|e| e.to_string())?;
+
     Ok(())
 }
 
-pub fn load_sets(root: &PathBuf, source: &str) -> Result<Vec<Vec<i32>>, String> {
+pub fn load_sets(root: &PathBuf, source: &str, format: RealFormat)
+    -> Result<Vec<DatafileSet>, String>
+{
     let cache_path = root.join(source.to_string() + CACHE_EXT);
 
     let sets = if let Ok(cache) = File::open(&cache_path) {
@@ -55,23 +79,25 @@ pub fn load_sets(root: &PathBuf, source: &str) -> Result<Vec<Vec<i32>>, String>
     }
     else {
         println!("Cache not found, building...");
-        parse_and_cache_webdocs(root, source, &cache_path)?
+        parse_and_cache(root, source, format, &cache_path)?
     };
 
     Ok(sets)
 }
 
-fn parse_and_cache_webdocs(root: &PathBuf, source: &str, cache_path: &PathBuf)
-    -> Result<Vec<DatafileSet>, String>
+fn parse_and_cache(
+    root: &PathBuf,
+    source: &str,
+    format: RealFormat,
+    cache_path: &PathBuf) -> Result<Vec<DatafileSet>, String>
 {
-    let text_path = root.join(source.to_string() + TEXT_FILE_EXT);
-    let text_file = File::open(&text_path)
-        .map_err(|e|
-            fmt_open_err(e, &text_path) +
-            ", did you run ./scripts/fetch_*.bash ?"
-        )?;
-
-    let sets = parse_text(text_file)?;
+    let sets = match format {
+        RealFormat::Webdocs => parse_text(open_raw(&root.join(source.to_string() + ".dat"))?)?,
+        RealFormat::Snap => parse_snap(open_raw(&root.join(source.to_string() + ".txt"))?)?,
+        RealFormat::Roaring => parse_roaring_dir(
+            &root.join("roaring").join(source.to_string() + "_srt"))?,
+        RealFormat::Csv => parse_csv(open_raw(&root.join(source.to_string() + ".csv"))?)?,
+    };
 
     println!("Writing cache...");
 
@@ -89,9 +115,16 @@ fn parse_and_cache_webdocs(root: &PathBuf, source: &str, cache_path: &PathBuf)
     Ok(sets)
 }
 
-fn parse_text(text: File) -> Result<Vec<DatafileSet>, String> {
-    let reader = BufReader::new(text);
+fn open_raw(path: &PathBuf) -> Result<BufReader<File>, String> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|e|
+            fmt_open_err(e, path) +
+            ", did you run ./scripts/realdata/fetch_*.bash ?"
+        )
+}
 
+fn parse_text(reader: impl BufRead) -> Result<Vec<DatafileSet>, String> {
     reader
         .lines()
         .map(|line| parse_line(
@@ -103,26 +136,111 @@ fn parse_text(text: File) -> Result<Vec<DatafileSet>, String> {
 fn parse_line(line: String) -> Result<DatafileSet, String> {
     line
         .split_ascii_whitespace()
-        .map(|number| number.parse::<i32>()
-            .map_err(|e| format!("unable to parse integer: {}", e.to_string()))
-        )
+        .map(parse_int)
         .collect()
 }
 
+/// Parses a SNAP edge list into the sorted, deduplicated out-neighbourhood of
+/// each source vertex. Sets are ordered from largest to smallest.
+fn parse_snap(reader: impl BufRead) -> Result<Vec<DatafileSet>, String> {
+    let mut adjacency: BTreeMap<i32, DatafileSet> = BTreeMap::new();
+
+    for line in reader.lines() {
+        let line = line.map_err(|e| format!("unable to read line: {}", e.to_string()))?;
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+
+        let mut fields = line.split_ascii_whitespace();
+        let (from, to) = match (fields.next(), fields.next()) {
+            (Some(from), Some(to)) => (parse_int(from)?, parse_int(to)?),
+            _ => return Err(format!("invalid edge: {}", line)),
+        };
+        adjacency.entry(from).or_default().push(to);
+    }
+
+    let mut sets: Vec<DatafileSet> = adjacency.into_values()
+        .map(|mut set| {
+            set.sort_unstable();
+            set.dedup();
+            set
+        })
+        .collect();
+
+    sets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+    Ok(sets)
+}
+
+/// Parses a directory of CRoaring benchmark bitmaps, each a comma-separated
+/// `.txt` file. Files are read in name order so the cache is reproducible.
+fn parse_roaring_dir(dir: &PathBuf) -> Result<Vec<DatafileSet>, String> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e|
+            fmt_open_err(e, dir) +
+            ", did you run ./scripts/realdata/fetch_roaring.sh ?"
+        )?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    paths.sort();
+
+    let mut sets = Vec::with_capacity(paths.len());
+    for path in paths {
+        sets.extend(parse_csv(open_raw(&path)?)?);
+    }
+    Ok(sets)
+}
+
+fn parse_csv(reader: impl BufRead) -> Result<Vec<DatafileSet>, String> {
+    let mut sets = Vec::new();
+
+    for line in reader.lines() {
+        let line = line.map_err(|e| format!("unable to read line: {}", e.to_string()))?;
+        let mut set = line
+            .split(',')
+            .map(str::trim)
+            .filter(|field| !field.is_empty())
+            .map(parse_int)
+            .collect::<Result<DatafileSet, String>>()?;
+
+        if !set.is_empty() {
+            set.sort_unstable();
+            set.dedup();
+            sets.push(set);
+        }
+    }
+    Ok(sets)
+}
+
+fn parse_int(number: &str) -> Result<i32, String> {
+    number.parse::<i32>()
+        .map_err(|e| format!("unable to parse integer {}: {}", number, e.to_string()))
+}
+
 fn generate_real_intersection(
     all_sets: &Vec<DatafileSet>,
+    info: &RealDataset,
     xdir: &PathBuf,
     set_count: usize,
-    i: usize) -> Result<(), String>
+    i: usize,
+    rng: &mut impl Rng) -> Result<QueryStats, String>
 {
-    let rng = &mut thread_rng();
+    let (sets, stats) = (0..MAX_SAMPLE_ATTEMPTS)
+        .find_map(|_| {
+            let mut sets = sample_sets(all_sets, &info.sampling, set_count, rng)?;
+            sets.sort_by_key(|&s| s.len());
 
-    let mut sets: Vec<&DatafileSet> = all_sets
-        .choose_multiple(rng, set_count)
-        .collect();
+            let stats = query_stats(&sets);
+            let accept = info.min_selectivity.is_none_or(|min|
+                stats.result_len > 0 && stats.selectivity * PERCENT_F >= min as f64
+            );
+            accept.then_some((sets, stats))
+        })
+        .ok_or_else(|| format!(
+            "unable to sample {} sets matching {:?} (min selectivity {:?}) after {} attempts",
+            set_count, info.sampling, info.min_selectivity, MAX_SAMPLE_ATTEMPTS
+        ))?;
 
-    sets.sort_by_key(|&s| s.len());
-    
     let pair_path = xdir.join(i.to_string());
 
     let dataset_file = File::create(&pair_path)
@@ -134,6 +252,122 @@ fn generate_real_intersection(
 
     datafile::to_writer(dataset_file, &sets)
         .map_err(|e| e.to_string())?;
-    
-    Ok(())
+
+    Ok(stats)
+}
+
+/// Chooses `count` distinct sets according to `sampling`, or `None` if this
+/// attempt failed to find suitable sets.
+fn sample_sets<'a>(
+    all_sets: &'a [DatafileSet],
+    sampling: &Sampling,
+    count: usize,
+    rng: &mut impl Rng) -> Option<Vec<&'a DatafileSet>>
+{
+    let candidates: Vec<&DatafileSet> = match sampling {
+        Sampling::Uniform => all_sets.iter().collect(),
+        Sampling::SizeBucket { min_len, max_len } => all_sets.iter()
+            .filter(|s| (*min_len..=*max_len).contains(&s.len()))
+            .collect(),
+        Sampling::Skew { ratio } => {
+            // Fix the smallest and largest sets, then fill in with sets
+            // between the two so that the skew is not exceeded.
+            let small = all_sets.choose(rng)?;
+            let target = small.len() as f64 * ratio;
+            let large = all_sets.iter()
+                .filter(|s| (target / SKEW_TOLERANCE..=target * SKEW_TOLERANCE)
+                    .contains(&(s.len() as f64)))
+                .filter(|&s| !std::ptr::eq(s, small))
+                .collect::<Vec<_>>()
+                .choose(rng)
+                .copied()?;
+
+            let (min_len, max_len) = (small.len(), large.len());
+            let middle: Vec<&DatafileSet> = all_sets.iter()
+                .filter(|&s| !std::ptr::eq(s, small) && !std::ptr::eq(s, large))
+                .filter(|s| (min_len..=max_len).contains(&s.len()))
+                .collect();
+
+            let mut sets = vec![small, large];
+            sets.extend(middle.choose_multiple(rng, count.saturating_sub(2)));
+            sets.truncate(count);
+            return (sets.len() == count).then_some(sets);
+        },
+        Sampling::CoOccurring => {
+            let element = all_sets.choose(rng)?.choose(rng)?;
+            all_sets.iter()
+                .filter(|s| s.binary_search(element).is_ok())
+                .collect()
+        },
+    };
+
+    (candidates.len() >= count)
+        .then(|| candidates.choose_multiple(rng, count).copied().collect())
+}
+
+fn query_stats(sets: &[&DatafileSet]) -> QueryStats {
+    let sizes: Vec<usize> = sets.iter().map(|s| s.len()).collect();
+    let result_len = intersect::run_svs(sets, intersect::branchless_merge).len();
+
+    let min_len = sizes.iter().copied().min().unwrap_or(0);
+    let max_len = sizes.iter().copied().max().unwrap_or(0);
+
+    QueryStats {
+        sizes,
+        result_len,
+        selectivity: if min_len == 0 { 0.0 } else { result_len as f64 / min_len as f64 },
+        skew: if min_len == 0 { 0.0 } else { max_len as f64 / min_len as f64 },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snap_dedup_sort() {
+        let text = "# Directed graph\n# FromNodeId\tToNodeId\n1\t5\n1\t3\n2\t7\n1\t5\n";
+        let sets = parse_snap(text.as_bytes()).unwrap();
+        assert_eq!(sets, vec![vec![3, 5], vec![7]]);
+    }
+
+    #[test]
+    fn csv_sets() {
+        let text = "4,1,2\n\n9, 8 ,8\n";
+        let sets = parse_csv(text.as_bytes()).unwrap();
+        assert_eq!(sets, vec![vec![1, 2, 4], vec![8, 9]]);
+
+        assert!(parse_csv("1,x".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn webdocs_lines() {
+        let sets = parse_text("1 2 3\n4 5\n".as_bytes()).unwrap();
+        assert_eq!(sets, vec![vec![1, 2, 3], vec![4, 5]]);
+    }
+
+    #[test]
+    fn sampling_strategies() {
+        let all_sets: Vec<DatafileSet> = (1..=40)
+            .map(|len| (0..len).map(|i| i * 3 + len % 5).collect())
+            .collect();
+        let rng = &mut thread_rng();
+
+        for _ in 0..100 {
+            let bucket = Sampling::SizeBucket { min_len: 10, max_len: 20 };
+            let sets = sample_sets(&all_sets, &bucket, 3, rng).unwrap();
+            assert!(sets.iter().all(|s| (10..=20).contains(&s.len())));
+
+            let skew = Sampling::Skew { ratio: 8.0 };
+            if let Some(mut sets) = sample_sets(&all_sets, &skew, 3, rng) {
+                sets.sort_by_key(|s| s.len());
+                let achieved = query_stats(&sets).skew;
+                assert!((8.0 / SKEW_TOLERANCE..=8.0 * SKEW_TOLERANCE).contains(&achieved));
+            }
+
+            if let Some(sets) = sample_sets(&all_sets, &Sampling::CoOccurring, 4, rng) {
+                assert!(query_stats(&sets).result_len > 0);
+            }
+        }
+    }
 }
@@ -80,9 +80,62 @@ pub enum Parameter {
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct RealDataset {
     pub source: String,
+    #[serde(default)]
+    pub format: RealFormat,
     pub gen_count: usize,
     pub set_count_start: u32,
     pub set_count_end: u32,
+    #[serde(default)]
+    pub sampling: Sampling,
+    /// Reject queries whose result is smaller than this fraction (in units of
+    /// 1/PERCENT) of the smallest set. Any value, including `Some(0)`, also
+    /// rejects empty results.
+    pub min_selectivity: Option<u32>,
+}
+
+/// How the sets of each real query are chosen from the dataset.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+#[serde(rename_all = "snake_case", tag = "strategy")]
+pub enum Sampling {
+    #[default]
+    Uniform,
+    /// All sets have a length within [min_len, max_len].
+    SizeBucket { min_len: usize, max_len: usize },
+    /// The largest set is approximately `ratio` times the size of the smallest.
+    Skew { ratio: f64 },
+    /// All sets share at least one element.
+    CoOccurring,
+}
+
+/// Achieved properties of a single sampled real query, recorded alongside the
+/// dataset in `queries.json`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct QueryStats {
+    pub sizes: Vec<usize>,
+    pub result_len: usize,
+    /// Result length relative to the smallest set.
+    pub selectivity: f64,
+    /// Largest set length relative to the smallest set.
+    pub skew: f64,
+}
+
+/// Query statistics for a real dataset, keyed by set count.
+pub type RealQueryStats = HashMap<u32, Vec<QueryStats>>;
+
+/// Format of the raw files a real dataset is parsed from, relative to the
+/// datasets directory.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RealFormat {
+    /// `<source>.dat`: one set per line, whitespace-separated (FIMI webdocs).
+    #[default]
+    Webdocs,
+    /// `<source>.txt`: SNAP edge list, one set per source vertex.
+    Snap,
+    /// `roaring/<source>_srt/*.txt`: CRoaring benchmark bitmap dumps.
+    Roaring,
+    /// `<source>.csv`: one set per line, comma-separated.
+    Csv,
 }
 
 pub type SetPair = (Vec<i32>, Vec<i32>);
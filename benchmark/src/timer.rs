@@ -1,20 +1,22 @@
 pub mod harness;
 pub mod perf;
 
-use std::{simd::{*, cmp::*}, ops::BitAnd};
+use std::{simd::{*, cmp::*}, ops::BitAnd, collections::{HashSet, BTreeSet}};
 
 use setops::{
     intersect::{
-        self, Intersect2, Intersect2C, IntersectK,
-        fesia::{IntegerHash, FesiaTwoSetMethod, SimdType, HashScale, FesiaKSetMethod}
+        self, Intersect2, Intersect2C, Intersect2InPlace, IntersectK,
+        fesia::{IntegerHash, FesiaTwoSetMethod, SimdType, HashScale, FesiaKSetMethod, HashFamily},
     },
+    bsr::BsrVec,
+    Set, Intersect, Cardinality,
     visitor::{
         UnsafeWriter, Visitor, Counter,
         SimdVisitor4, SimdVisitor8, SimdVisitor16
     },
 };
-use crate::{datafile::DatafileSet, timer::harness::time_fesia_kset};
-use harness::{Harness, HarnessVisitor, RunResult, UnsafeIntersectBsr};
+use crate::{datafile::DatafileSet, util, timer::harness::time_fesia_kset};
+use harness::{Harness, HarnessVisitor, RunResult, UnsafeIntersectBsr, UnsafeIntersectKBsr, IntersectBsr64};
 
 type TwosetTimer = Box<dyn Fn(&mut Harness, &[i32], &[i32]) -> RunResult>;
 type KsetTimer = Box<dyn Fn(&mut Harness, &[DatafileSet]) -> RunResult>;
@@ -42,9 +44,11 @@ impl Timer {
     {
         try_parse_twoset::<V>(name)
             .or_else(|| try_parse_twoset_c(name))
+            .or_else(|| try_parse_twoset_inplace(name))
             .or_else(|| try_parse_bsr(name))
             .or_else(|| try_parse_kset::<V>(name))
             .or_else(|| try_parse_roaring(name, count_only))
+            .or_else(|| try_parse_set(name, count_only))
             .or_else(|| try_parse_fesia_hash::<V>(name))
             .or_else(|| try_parse_fesia::<V>(name))
     }
@@ -183,6 +187,38 @@ fn try_parse_twoset_c(name: &str) -> Option<Timer> {
         })
 }
 
+fn try_parse_twoset_inplace(name: &str) -> Option<Timer> {
+    let maybe_intersect: Option<Intersect2InPlace<i32>> = match name {
+        "galloping_inplace" => Some(intersect::galloping_inplace),
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        "shuffling_sse_inplace" => Some(intersect::shuffling_sse_inplace),
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        "shuffling_avx2_inplace" => Some(intersect::shuffling_avx2_inplace),
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        "shuffling_avx512_inplace" => Some(intersect::shuffling_avx512_inplace),
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        "broadcast_sse_inplace" => Some(intersect::broadcast_sse_inplace),
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        "broadcast_avx2_inplace" => Some(intersect::broadcast_avx2_inplace),
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        "broadcast_avx512_inplace" => Some(intersect::broadcast_avx512_inplace),
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        "galloping_sse_inplace" => Some(intersect::galloping_sse_inplace),
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        "galloping_avx2_inplace" => Some(intersect::galloping_avx2_inplace),
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        "galloping_avx512_inplace" => Some(intersect::galloping_avx512_inplace),
+        _ => None,
+    };
+    maybe_intersect.map(|i|
+        Timer {
+            twoset: Some(Box::new(
+                move |warmup, a, b| Ok(harness::time_twoset_inplace(warmup, a, b, i)))),
+            kset: Some(Box::new(
+                move |warmup, sets| harness::time_svs_inplace(warmup, sets, i))),
+        })
+}
+
 pub trait TwosetTimingSpec<V> {
     fn twoset_timer(i: Intersect2<[i32], V>) -> Timer;
 }
@@ -209,6 +245,37 @@ impl TwosetTimingSpec<Counter> for Counter {
 }
 
 fn try_parse_bsr(name: &str) -> Option<Timer> {
+    let maybe_kset: Option<UnsafeIntersectKBsr> = match name {
+        "small_adaptive_bsr" => Some(intersect::small_adaptive_bsr),
+        "merge_k_bsr"        => Some(intersect::merge_k_bsr),
+        _ => None,
+    };
+    if let Some(intersect) = maybe_kset {
+        return Some(Timer {
+            twoset: None,
+            kset: Some(Box::new(move |warmup, sets| harness::time_kset_bsr(warmup, sets, intersect))),
+        });
+    }
+
+    let maybe_bsr64: Option<IntersectBsr64> = match name {
+        "branchless_merge_bsr64" => Some(intersect::branchless_merge_bsr),
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        "shuffling_avx2_bsr64"   => Some(intersect::shuffling_avx2_bsr64),
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        "broadcast_avx2_bsr64"   => Some(intersect::broadcast_avx2_bsr64),
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        "shuffling_avx512_bsr64" => Some(intersect::shuffling_avx512_bsr64),
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        "broadcast_avx512_bsr64" => Some(intersect::broadcast_avx512_bsr64),
+        _ => None,
+    };
+    if let Some(intersect) = maybe_bsr64 {
+        return Some(Timer {
+            twoset: Some(Box::new(move |warmup, a, b| Ok(harness::time_bsr64(warmup, a, b, intersect)))),
+            kset: Some(Box::new(move |warmup, sets| harness::time_svs_bsr64(warmup, sets, intersect))),
+        });
+    }
+
     let maybe_intersect: Option<UnsafeIntersectBsr> = match name {
         "branchless_merge_bsr" => Some(intersect::branchless_merge_bsr),
         "galloping_bsr"        => Some(intersect::galloping_bsr),
@@ -254,7 +321,7 @@ fn try_parse_bsr(name: &str) -> Option<Timer> {
     };
     maybe_intersect.map(|intersect: UnsafeIntersectBsr| Timer {
         twoset: Some(Box::new(move |warmup, a, b| Ok(harness::time_bsr(warmup, a, b, intersect)))),
-        kset: None,
+        kset: Some(Box::new(move |warmup, sets| harness::time_svs_bsr(warmup, sets, intersect))),
     })
 }
 
@@ -275,6 +342,52 @@ where
     })
 }
 
+fn try_parse_set(name: &str, count_only: bool) -> Option<Timer> {
+    use intersect::fesia::*;
+
+    fn timer<S>(count_only: bool) -> Option<Timer>
+    where
+        S: Set<i32> + Intersect + Cardinality + 'static,
+    {
+        Some(Timer {
+            twoset: Some(Box::new(move |warmup, a, b| harness::time_set::<i32, S>(warmup, &[a, b], count_only))),
+            kset: Some(Box::new(move |warmup, sets| {
+                let sets: Vec<&[i32]> = sets.iter().map(|s| s.as_slice()).collect();
+                harness::time_set::<i32, S>(warmup, &sets, count_only)
+            })),
+        })
+    }
+
+    fn timer_u32<S>(count_only: bool) -> Option<Timer>
+    where
+        S: Set<u32> + Intersect + Cardinality + 'static,
+    {
+        Some(Timer {
+            twoset: Some(Box::new(move |warmup, a, b| harness::time_set::<u32, S>(
+                warmup, &[util::slice_i32_to_u32(a), util::slice_i32_to_u32(b)], count_only))),
+            kset: Some(Box::new(move |warmup, sets| {
+                let sets: Vec<&[u32]> = sets.iter().map(|s| util::slice_i32_to_u32(s)).collect();
+                harness::time_set::<u32, S>(warmup, &sets, count_only)
+            })),
+        })
+    }
+
+    match name {
+        "vec_set"   => timer::<Vec<i32>>(count_only),
+        "hash_set"  => timer::<HashSet<i32>>(count_only),
+        "btree_set" => timer::<BTreeSet<i32>>(count_only),
+        "bsr_set"   => timer_u32::<BsrVec>(count_only),
+        "bsr64_set" => timer_u32::<BsrVec<u64>>(count_only),
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        "fesia_sse_set"    => timer::<Fesia8Sse>(count_only),
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        "fesia_avx2_set"   => timer::<Fesia8Avx2>(count_only),
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        "fesia_avx512_set" => timer::<Fesia8Avx512>(count_only),
+        _ => None,
+    }
+}
+
 fn try_parse_roaring(name: &str, count_only: bool) -> Option<Timer> { 
     match name {
         "croaring_opt" => Some(Timer {
@@ -325,14 +438,18 @@ where
         return None;
     }
 
-    let prefix = &name[..last_underscore];
+    let (prefix, hash) = split_fesia_hash(&name[..last_underscore]);
 
     const FESIA: &str = "fesia";
+    const FESIA_KWAY: &str = "fesia_kway";
 
     use FesiaTwoSetMethod::*;
-    let (intersect, rest) =
-        if prefix.starts_with(FESIA) {
-            (SimilarSize, &prefix[FESIA.len()..])
+    let (intersect, intersect_kset, rest) =
+        if prefix.starts_with(FESIA_KWAY) {
+            (SimilarSize, FesiaKSetMethod::SimilarSizeSimd, &prefix[FESIA_KWAY.len()..])
+        }
+        else if prefix.starts_with(FESIA) {
+            (SimilarSize, FesiaKSetMethod::SimilarSize, &prefix[FESIA.len()..])
         }
         else {
             return None;
@@ -349,31 +466,40 @@ where
     match rest {
         #[cfg(all(feature = "simd", target_feature = "ssse3"))]
         "8_sse" =>
-            Some(gen_fesia_timer::<MixHash, i8, 16, V>(hash_scale, intersect, simd_type)),
+            Some(gen_fesia_timer_hash::<i8, 16, V>(
+                hash, hash_scale, intersect, intersect_kset, simd_type)),
         #[cfg(all(feature = "simd", target_feature = "ssse3"))]
         "16_sse" =>
-            Some(gen_fesia_timer::<MixHash, i16, 8, V>(hash_scale, intersect, simd_type)),
+            Some(gen_fesia_timer_hash::<i16, 8, V>(
+                hash, hash_scale, intersect, intersect_kset, simd_type)),
         #[cfg(all(feature = "simd", target_feature = "ssse3"))]
         "32_sse" =>
-            Some(gen_fesia_timer::<MixHash, i32, 4, V>(hash_scale, intersect, simd_type)),
+            Some(gen_fesia_timer_hash::<i32, 4, V>(
+                hash, hash_scale, intersect, intersect_kset, simd_type)),
         #[cfg(all(feature = "simd", target_feature = "avx2"))]
         "8_avx2" =>
-            Some(gen_fesia_timer::<MixHash, i8, 32, V>(hash_scale, intersect, simd_type)),
+            Some(gen_fesia_timer_hash::<i8, 32, V>(
+                hash, hash_scale, intersect, intersect_kset, simd_type)),
         #[cfg(all(feature = "simd", target_feature = "avx2"))]
         "16_avx2" =>
-            Some(gen_fesia_timer::<MixHash, i16, 16, V>(hash_scale, intersect, simd_type)),
+            Some(gen_fesia_timer_hash::<i16, 16, V>(
+                hash, hash_scale, intersect, intersect_kset, simd_type)),
         #[cfg(all(feature = "simd", target_feature = "avx2"))]
         "32_avx2" =>
-            Some(gen_fesia_timer::<MixHash, i32, 8, V>(hash_scale, intersect, simd_type)),
+            Some(gen_fesia_timer_hash::<i32, 8, V>(
+                hash, hash_scale, intersect, intersect_kset, simd_type)),
         #[cfg(all(feature = "simd", target_feature = "avx512f"))]
         "8_avx512" =>
-            Some(gen_fesia_timer::<MixHash, i8, 64, V>(hash_scale, intersect, simd_type)),
+            Some(gen_fesia_timer_hash::<i8, 64, V>(
+                hash, hash_scale, intersect, intersect_kset, simd_type)),
         #[cfg(all(feature = "simd", target_feature = "avx512f"))]
         "16_avx512" =>
-            Some(gen_fesia_timer::<MixHash, i16, 32, V>(hash_scale, intersect, simd_type)),
+            Some(gen_fesia_timer_hash::<i16, 32, V>(
+                hash, hash_scale, intersect, intersect_kset, simd_type)),
         #[cfg(all(feature = "simd", target_feature = "avx512f"))]
         "32_avx512" =>
-            Some(gen_fesia_timer::<MixHash, i32, 16, V>(hash_scale, intersect, simd_type)),
+            Some(gen_fesia_timer_hash::<i32, 16, V>(
+                hash, hash_scale, intersect, intersect_kset, simd_type)),
         _ => None,
     };
 
@@ -398,14 +524,14 @@ where
         return None;
     }
 
-    let prefix = &name[..last_underscore];
+    let (prefix, hash) = split_fesia_hash(&name[..last_underscore]);
 
     const FESIA_HASH: &str = "fesia_hash";
 
     use FesiaTwoSetMethod::*;
-    let (intersect, rest) =
+    let (intersect, intersect_kset, rest) =
         if prefix.starts_with(FESIA_HASH) {
-            (Skewed, &prefix[FESIA_HASH.len()..])
+            (Skewed, FesiaKSetMethod::Skewed, &prefix[FESIA_HASH.len()..])
         }
         else {
             return None;
@@ -414,18 +540,65 @@ where
     use SimdType::*;
     let maybe_timer: Option<Timer> =
     match rest {
-        "8" => Some(gen_fesia_timer::<MixHash, i8, 16, V>(hash_scale, intersect, Sse)),
-        "16" => Some(gen_fesia_timer::<MixHash, i16, 8, V>(hash_scale, intersect, Sse)),
-        "32" => Some(gen_fesia_timer::<MixHash, i32, 4, V>(hash_scale, intersect, Sse)),
+        "8" => Some(gen_fesia_timer_hash::<i8, 16, V>(
+            hash, hash_scale, intersect, intersect_kset, Sse)),
+        "16" => Some(gen_fesia_timer_hash::<i16, 8, V>(
+            hash, hash_scale, intersect, intersect_kset, Sse)),
+        "32" => Some(gen_fesia_timer_hash::<i32, 4, V>(
+            hash, hash_scale, intersect, intersect_kset, Sse)),
         _ => None,
     };
 
     maybe_timer
 }
 
+// Splits an optional hash family suffix from a FESIA algorithm name, e.g.,
+// "fesia32_avx2_crc". The default is `MixHash`.
+fn split_fesia_hash(prefix: &str) -> (&str, HashFamily) {
+    prefix.rfind('_')
+        .and_then(|i| HashFamily::from_name(&prefix[i+1..]).map(|hash| (&prefix[..i], hash)))
+        .unwrap_or((prefix, HashFamily::Mix))
+}
+
+fn gen_fesia_timer_hash<S, const LANES: usize, V>(
+    hash: HashFamily,
+    hash_scale: HashScale,
+    intersect_method: FesiaTwoSetMethod,
+    intersect_kset: FesiaKSetMethod,
+    simd_type: SimdType)
+    -> Timer
+where
+    S: SimdElement + MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+    Simd<S, LANES>: BitAnd<Output=Simd<S, LANES>> + SimdPartialEq<Mask=Mask<S, LANES>>,
+    V: Visitor<i32> + SimdVisitor4 + SimdVisitor8 + SimdVisitor16 + HarnessVisitor
+{
+    use intersect::fesia::*;
+
+    match hash {
+        HashFamily::Identity =>
+            gen_fesia_timer::<IdentityHash, S, LANES, V>(
+                hash_scale, intersect_method, intersect_kset, simd_type),
+        HashFamily::Mix =>
+            gen_fesia_timer::<MixHash, S, LANES, V>(
+                hash_scale, intersect_method, intersect_kset, simd_type),
+        HashFamily::MultiplyShift =>
+            gen_fesia_timer::<MultiplyShiftHash, S, LANES, V>(
+                hash_scale, intersect_method, intersect_kset, simd_type),
+        #[cfg(target_feature = "sse4.2")]
+        HashFamily::Crc32 =>
+            gen_fesia_timer::<Crc32Hash, S, LANES, V>(
+                hash_scale, intersect_method, intersect_kset, simd_type),
+        HashFamily::Tabulation =>
+            gen_fesia_timer::<TabulationHash, S, LANES, V>(
+                hash_scale, intersect_method, intersect_kset, simd_type),
+    }
+}
+
 fn gen_fesia_timer<H, S, const LANES: usize, V>(
     hash_scale: HashScale,
     intersect_method: FesiaTwoSetMethod,
+    intersect_kset: FesiaKSetMethod,
     simd_type: SimdType)
     -> Timer
 where
@@ -435,16 +608,13 @@ where
     Simd<S, LANES>: BitAnd<Output=Simd<S, LANES>> + SimdPartialEq<Mask=Mask<S, LANES>>,
     V: Visitor<i32> + SimdVisitor4 + SimdVisitor8 + SimdVisitor16 + HarnessVisitor
 {
-    // TODO: k-set skewed intersect
-    let intersect_kset = FesiaKSetMethod::SimilarSize;
-
     use harness::time_fesia;
 
     Timer {
         twoset: Some(Box::new(move |warmup, a, b|
             time_fesia::<H, S, LANES, V>(warmup, a, b, hash_scale, intersect_method, simd_type))),
         kset: Some(Box::new(move |warmup, sets|
-            time_fesia_kset::<H, S, LANES, V>(warmup, sets, hash_scale, intersect_kset)))
+            time_fesia_kset::<H, S, LANES, V>(warmup, sets, hash_scale, intersect_kset, simd_type)))
     }
 }
 
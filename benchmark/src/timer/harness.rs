@@ -3,18 +3,20 @@ use std::{
     hint, simd::{*, cmp::*}, ops::BitAnd,
 };
 use setops::{
-    intersect::{Intersect2, Intersect2C, IntersectK, fesia::*, self},
+    intersect::{Intersect2, Intersect2C, Intersect2InPlace, IntersectK, fesia::*, self},
     visitor::{
         Visitor, SimdVisitor4, SimdVisitor8, SimdVisitor16,
         UnsafeWriter, UnsafeBsrWriter, Counter
     },
     bsr::{BsrVec, BsrRef},
-    Set,
+    Set, Cardinality,
 };
 use crate::{datafile::DatafileSet, util, timer::perf::*};
 
 pub type RunResult = Result<Run, String>;
 pub type UnsafeIntersectBsr = for<'a> fn(set_a: BsrRef<'a>, set_b: BsrRef<'a>, visitor: &mut UnsafeBsrWriter);
+pub type UnsafeIntersectKBsr = for<'a> fn(sets: &[BsrRef<'a>], visitor: &mut UnsafeBsrWriter);
+pub type IntersectBsr64 = for<'a> fn(set_a: BsrRef<'a, u64>, set_b: BsrRef<'a, u64>, visitor: &mut BsrVec<u64>);
 
 pub struct Run {
     pub time: Duration,
@@ -102,7 +104,7 @@ pub fn time_twoset_c(
     set_b: &[i32],
     intersect: Intersect2C<[i32]>) -> Run
 {
-    let capacity = set_a.len().min(set_b.len());
+    let capacity = set_a.len().min(set_b.len()) + intersect::RESULT_SLACK;
 
     let prepare = || vec![0;capacity];
     let run = |result: &mut Vec<i32>| _ = intersect(set_a, set_b, result.as_mut_slice());
@@ -112,6 +114,20 @@ pub fn time_twoset_c(
     elapsed
 }
 
+pub fn time_twoset_inplace(
+    harness: &mut Harness,
+    set_a: &[i32],
+    set_b: &[i32],
+    intersect: Intersect2InPlace<i32>) -> Run
+{
+    let prepare = || set_a.to_vec();
+    let run = |result: &mut Vec<i32>| _ = intersect(result.as_mut_slice(), set_b);
+
+    let (elapsed, _result) = harness.time(prepare, run);
+
+    elapsed
+}
+
 pub fn time_bsr(
     harness: &mut Harness,
     set_a: &[i32],
@@ -131,6 +147,131 @@ pub fn time_bsr(
     elapsed
 }
 
+pub fn time_svs_bsr(
+    harness: &mut Harness,
+    sets: &[DatafileSet],
+    intersect: UnsafeIntersectBsr) -> RunResult
+{
+    let bsr_sets: Vec<BsrVec> = sets.iter()
+        .map(|s| BsrVec::from_sorted(util::slice_i32_to_u32(s)))
+        .collect();
+    let bsr_refs: Vec<BsrRef> = bsr_sets.iter().map(|s| s.bsr_ref()).collect();
+
+    // Note: max() required here
+    let capacity = bsr_sets.iter().map(|s| s.len()).max()
+        .ok_or_else(|| "cannot intersect 0 sets".to_string())?;
+
+    let prepare = || (
+        UnsafeBsrWriter::with_capacities(capacity),
+        UnsafeBsrWriter::with_capacities(capacity)
+    );
+    let run = |(left, right): &mut _| {
+        intersect::svs_bsr(&bsr_refs, left, right, intersect);
+    };
+
+    let (elapsed, _) = harness.time(prepare, run);
+
+    Ok(elapsed)
+}
+
+pub fn time_bsr64(
+    harness: &mut Harness,
+    set_a: &[i32],
+    set_b: &[i32],
+    intersect: IntersectBsr64) -> Run
+{
+    let bsr_a: BsrVec<u64> = BsrVec::from_sorted(util::slice_i32_to_u32(set_a));
+    let bsr_b: BsrVec<u64> = BsrVec::from_sorted(util::slice_i32_to_u32(set_b));
+
+    let capacity = bsr_a.len().min(bsr_b.len());
+
+    let prepare = || BsrVec::with_capacities(capacity);
+    let run = |writer: &mut _| intersect(bsr_a.bsr_ref(), bsr_b.bsr_ref(), writer);
+
+    let (elapsed, _writer) = harness.time(prepare, run);
+
+    elapsed
+}
+
+pub fn time_svs_bsr64(
+    harness: &mut Harness,
+    sets: &[DatafileSet],
+    intersect: IntersectBsr64) -> RunResult
+{
+    let bsr_sets: Vec<BsrVec<u64>> = sets.iter()
+        .map(|s| BsrVec::from_sorted(util::slice_i32_to_u32(s)))
+        .collect();
+    let bsr_refs: Vec<BsrRef<u64>> = bsr_sets.iter().map(|s| s.bsr_ref()).collect();
+
+    let capacity = bsr_sets.iter().map(|s| s.len()).max()
+        .ok_or_else(|| "cannot intersect 0 sets".to_string())?;
+
+    let prepare = || (
+        BsrVec::with_capacities(capacity),
+        BsrVec::with_capacities(capacity)
+    );
+    let run = |(left, right): &mut _| {
+        intersect::svs_bsr(&bsr_refs, left, right, intersect);
+    };
+
+    let (elapsed, _) = harness.time(prepare, run);
+
+    Ok(elapsed)
+}
+
+/// Times any representation through the `Intersect` trait. Sets are built
+/// before timing; k-set intersections fold pairwise from the first set.
+pub fn time_set<T, S>(
+    harness: &mut Harness,
+    sets: &[&[T]],
+    count_only: bool) -> RunResult
+where
+    T: Clone,
+    S: Set<T> + setops::Intersect + Cardinality,
+{
+    if sets.len() < 2 {
+        return Err("cannot intersect fewer than 2 sets".to_string());
+    }
+    let sets: Vec<S> = sets.iter().map(|s| S::from_sorted(s)).collect();
+
+    let run = |_: &mut ()| {
+        if count_only && sets.len() == 2 {
+            hint::black_box(sets[0].intersect_count(&sets[1]));
+            return;
+        }
+        let result = sets[2..].iter()
+            .fold(sets[0].intersect(&sets[1]), |result, set| result.intersect(set));
+        hint::black_box(result.cardinality());
+    };
+
+    let (elapsed, _) = harness.time(|| (), run);
+
+    Ok(elapsed)
+}
+
+pub fn time_kset_bsr(
+    harness: &mut Harness,
+    sets: &[DatafileSet],
+    intersect: UnsafeIntersectKBsr) -> RunResult
+{
+    let bsr_sets: Vec<BsrVec> = sets.iter()
+        .map(|s| BsrVec::from_sorted(util::slice_i32_to_u32(s)))
+        .collect();
+    let mut bsr_refs: Vec<BsrRef> = bsr_sets.iter().map(|s| s.bsr_ref()).collect();
+    // As in `safe::intersect_k_bsr`, kernels expect the smallest set first.
+    bsr_refs.sort_by_key(|s| s.len());
+
+    let capacity = bsr_sets.iter().map(|s| s.len()).min()
+        .ok_or_else(|| "cannot intersect 0 sets".to_string())?;
+
+    let prepare = || UnsafeBsrWriter::with_capacities(capacity);
+    let run = |writer: &mut _| intersect(&bsr_refs, writer);
+
+    let (elapsed, _writer) = harness.time(prepare, run);
+
+    Ok(elapsed)
+}
+
 pub fn time_kset<V>(
     harness: &mut Harness,
     sets: &[DatafileSet],
@@ -156,7 +297,8 @@ pub fn time_svs<V>(
 {
     // Note: max() required here
     let capacity = sets.iter().map(|s| s.len()).max()
-        .ok_or_else(|| "cannot intersect 0 sets".to_string())?;
+        .ok_or_else(|| "cannot intersect 0 sets".to_string())?
+        + intersect::RESULT_SLACK;
 
     let prepare = || (
         UnsafeWriter::with_capacity(capacity),
@@ -193,6 +335,24 @@ pub fn time_svs_c(
     Ok(elapsed)
 }
 
+pub fn time_svs_inplace(
+    harness: &mut Harness,
+    sets: &[DatafileSet],
+    intersect: Intersect2InPlace<i32>) -> RunResult
+{
+    let capacity = sets.first().map(|s| s.len())
+        .ok_or_else(|| "cannot intersect 0 sets".to_string())?;
+
+    let prepare = || vec![0; capacity];
+    let run = |out: &mut Vec<i32>| {
+        intersect::svs_generic_inplace(sets, out, intersect);
+    };
+
+    let (elapsed, _) = harness.time(prepare, run);
+
+    Ok(elapsed)
+}
+
 pub fn time_croaring_2set(
     harness: &mut Harness,
     set_a: &[i32],
@@ -323,8 +483,8 @@ where
     let capacity = set_a.len().min(set_b.len());
     assert!(set_a.len() <= set_b.len());
 
-    let set_a: Fesia<H, S, LANES> = Fesia::from_sorted(set_a, hash_scale);
-    let set_b: Fesia<H, S, LANES> = Fesia::from_sorted(set_b, hash_scale);
+    let set_a: Fesia<H, S, LANES> = Fesia::from_sorted_scaled(set_a, hash_scale);
+    let set_b: Fesia<H, S, LANES> = Fesia::from_sorted_scaled(set_b, hash_scale);
 
     let prepare = || V::with_capacity(capacity);
 
@@ -361,7 +521,8 @@ pub fn time_fesia_kset<H, S, const LANES: usize, V>(
     harness: &mut Harness,
     sets: &[DatafileSet],
     hash_scale: HashScale,
-    intersect_method: FesiaKSetMethod)
+    intersect_method: FesiaKSetMethod,
+    simd_type: SimdType)
     -> RunResult
 where
     H: IntegerHash,
@@ -374,16 +535,31 @@ where
         .ok_or_else(|| "cannot intersect 0 sets".to_string())?;
 
     let fesia_sets: Vec<Fesia<H, S, LANES>> = sets.iter()
-        .map(|s| Fesia::from_sorted(s, hash_scale))
+        .map(|s| Fesia::from_sorted_scaled(s, hash_scale))
         .collect();
 
     let prepare = || V::with_capacity(capacity);
 
     use FesiaKSetMethod::*;
+    use SimdType::*;
 
-    let (elapsed, _) = match intersect_method {
-        SimilarSize => harness.time(prepare,
+    let (elapsed, _) = match (intersect_method, simd_type) {
+        (SimilarSize, _) => harness.time(prepare,
             |writer: &mut _| Fesia::<H, S, LANES>::intersect_k(&fesia_sets, writer)),
+        #[cfg(target_feature = "ssse3")]
+        (SimilarSizeSimd, Sse) => harness.time(prepare, |writer: &mut _|
+            Fesia::<H, S, LANES>::intersect_k_with::<SegmentBroadcastK<4>, _>(&fesia_sets, writer)),
+        #[cfg(target_feature = "avx2")]
+        (SimilarSizeSimd, Avx2) => harness.time(prepare, |writer: &mut _|
+            Fesia::<H, S, LANES>::intersect_k_with::<SegmentBroadcastK<8>, _>(&fesia_sets, writer)),
+        #[cfg(target_feature = "avx512f")]
+        (SimilarSizeSimd, Avx512) => harness.time(prepare, |writer: &mut _|
+            Fesia::<H, S, LANES>::intersect_k_with::<SegmentBroadcastK<16>, _>(&fesia_sets, writer)),
+        #[allow(unreachable_patterns)]
+        (SimilarSizeSimd, width) =>
+            return Err(format!("fesia SimilarSizeSimd does not support {:?}", width)),
+        (Skewed, _) => harness.time(prepare,
+            |writer: &mut _| Fesia::<H, S, LANES>::hash_intersect_k(&fesia_sets, writer)),
     };
 
     Ok(elapsed)
@@ -218,8 +218,10 @@ int intersect_qfilter_uint_b4(const int *set_a, int size_a,
     return size_c;
 }
 
-int intersect_qfilter_uint_b4_v2(const int *set_a, int size_a,
-        const int *set_b, int size_b, int *set_c)
+// Item is the type items are ordered as; vectors only compare for equality.
+template <typename Item>
+static int qfilter_b4_v2(const Item *set_a, int size_a,
+        const Item *set_b, int size_b, Item *set_c)
 {
     int i = 0, j = 0, size_c = 0;
     int qs_a = size_a - (size_a & 3);
@@ -259,8 +261,8 @@ int intersect_qfilter_uint_b4_v2(const int *set_a, int size_a,
             size_c += _mm_popcnt_u32(mask);           
         }
     
-        int a_max = set_a[i + 3];
-        int b_max = set_b[j + 3];
+        Item a_max = set_a[i + 3];
+        Item b_max = set_b[j + 3];
         if (a_max <= b_max) {
             i += 4;
             v_a = _mm_load_si128((__m128i*)(set_a + i));
@@ -289,6 +291,18 @@ int intersect_qfilter_uint_b4_v2(const int *set_a, int size_a,
     return size_c;
 }
 
+int intersect_qfilter_uint_b4_v2(const int *set_a, int size_a,
+        const int *set_b, int size_b, int *set_c)
+{
+    return qfilter_b4_v2(set_a, size_a, set_b, size_b, set_c);
+}
+
+int intersect_qfilter_u32_b4_v2(const uint32_t *set_a, int size_a,
+        const uint32_t *set_b, int size_b, uint32_t *set_c)
+{
+    return qfilter_b4_v2(set_a, size_a, set_b, size_b, set_c);
+}
+
 int intersect_qfilter_bsr_b4(const int* bases_a, const int* states_a, int size_a,
             const int* bases_b, const int* states_b, int size_b,
             int* bases_c, int* states_c)
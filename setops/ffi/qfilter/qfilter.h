@@ -1,5 +1,7 @@
 #pragma once
 
+#include <stdint.h>
+
 #ifdef __cplusplus
 extern "C" {
 #endif
@@ -15,6 +17,12 @@ int intersect_qfilter_uint_b4_v2(
             const int *set_b, int size_b,
             int *set_c);
 
+// As intersect_qfilter_uint_b4_v2, ordering items as unsigned.
+int intersect_qfilter_u32_b4_v2(
+            const uint32_t *set_a, int size_a,
+            const uint32_t *set_b, int size_b,
+            uint32_t *set_c);
+
 // QFilter+BSR:
 int intersect_qfilter_bsr_b4(
             const int* bases_a, const int* states_a, int size_a,
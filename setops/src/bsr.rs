@@ -10,16 +10,45 @@
 /// A significant portion of the implementation is derived from
 /// https://github.com/pkumod/GraphSetIntersection (MIT License)
 
-use std::{slice, iter::Zip};
-use crate::Set;
+mod ops;
 
-pub type Intersect2Bsr = for<'a> fn(set_a: BsrRef<'a>, set_b: BsrRef<'a>, visitor: &mut BsrVec);
-pub struct BsrRef<'a> {
+pub use ops::*;
+
+use std::{slice, iter::Zip, fmt::Debug};
+use num::PrimInt;
+use crate::{Set, Intersect, Cardinality, IterSorted, intersect, visitor::Counter};
+
+pub type Intersect2Bsr<S = u32> = for<'a> fn(set_a: BsrRef<'a, S>, set_b: BsrRef<'a, S>, visitor: &mut BsrVec<S>);
+pub type Intersect2BsrV<V> = for<'a> fn(set_a: BsrRef<'a>, set_b: BsrRef<'a>, visitor: &mut V);
+
+/// Word holding the items of one base. 64-bit states cover twice as many
+/// values per base, which compresses dense sets further.
+pub trait BsrState: PrimInt + Default + Debug {
+    const BITS: u32;
+    const SHIFT: u32 = Self::BITS.trailing_zeros();
+    const MASK: u32 = Self::BITS - 1;
+
+    /// State with only the bit for `item` set.
+    fn bit(item: u32) -> Self {
+        Self::one() << (item & Self::MASK) as usize
+    }
+}
+
+impl BsrState for u32 {
+    const BITS: u32 = u32::BITS;
+}
+
+impl BsrState for u64 {
+    const BITS: u32 = u64::BITS;
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct BsrRef<'a, S = u32> {
     pub bases: &'a[u32],
-    pub states: &'a[u32],
+    pub states: &'a[S],
 }
 
-impl<'a> BsrRef<'a> {
+impl<'a, S: BsrState> BsrRef<'a, S> {
     pub fn len(&self) -> usize {
         debug_assert!(self.bases.len() == self.states.len());
         self.bases.len()
@@ -30,15 +59,15 @@ impl<'a> BsrRef<'a> {
         self.bases.is_empty()
     }
 
-    pub fn advanced_by(self, offset: usize) -> BsrRef<'a> {
-        BsrRef::<'a> {
+    pub fn advanced_by(self, offset: usize) -> BsrRef<'a, S> {
+        BsrRef::<'a, S> {
             bases: &self.bases[offset..],
             states: &self.states[offset..],
         }
     }
 
-    pub unsafe fn advanced_by_unchecked(self, offset: usize) -> BsrRef<'a> {
-        BsrRef::<'a> {
+    pub unsafe fn advanced_by_unchecked(self, offset: usize) -> BsrRef<'a, S> {
+        BsrRef::<'a, S> {
             bases: self.bases.get_unchecked(offset..),
             states: self.states.get_unchecked(offset..),
         }
@@ -46,12 +75,12 @@ impl<'a> BsrRef<'a> {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct BsrVec {
+pub struct BsrVec<S = u32> {
     pub bases: Vec<u32>,
-    pub states: Vec<u32>,
+    pub states: Vec<S>,
 }
 
-impl BsrVec {
+impl<S: BsrState> BsrVec<S> {
     pub fn new() -> Self {
         Self {
             bases: Vec::new(),
@@ -66,8 +95,8 @@ impl BsrVec {
         }
     }
 
-    pub fn append(&mut self, base: u32, state: u32) {
-        debug_assert!(state != 0);
+    pub fn append(&mut self, base: u32, state: S) {
+        debug_assert!(state != S::zero());
         debug_assert!(self.bases.last().map(|b| b < &base).unwrap_or(true));
 
         self.bases.push(base);
@@ -78,20 +107,20 @@ impl BsrVec {
         let mut result = Vec::new();
         let iter = self.bases.iter().copied().zip(self.states.iter().copied());
         for (base, mut state) in iter {
-            let high = base << BSR_SHIFT;
-            while state != 0 {
+            let high = base << S::SHIFT;
+            while state != S::zero() {
                 result.push(high | state.trailing_zeros());
-                state &= state - 1;
+                state = state & (state - S::one());
             }
         }
         result
     }
 
-    pub fn iter(&self) -> Zip<slice::Iter<'_, u32>, slice::Iter<'_, u32>> {
+    pub fn iter(&self) -> Zip<slice::Iter<'_, u32>, slice::Iter<'_, S>> {
         self.bases.iter().zip(self.states.iter())
     }
 
-    pub fn bsr_ref(&self) -> BsrRef {
+    pub fn bsr_ref(&self) -> BsrRef<'_, S> {
         BsrRef {
             bases: &self.bases,
             states: &self.states,
@@ -107,25 +136,30 @@ impl BsrVec {
         debug_assert_eq!(self.bases.is_empty(), self.states.is_empty());
         self.bases.is_empty()
     }
+
+    pub fn clear(&mut self) {
+        self.bases.clear();
+        self.states.clear();
+    }
 }
 
-impl Default for BsrVec {
+impl<S: BsrState> Default for BsrVec<S> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<'a> IntoIterator for BsrRef<'a> {
-    type Item = (&'a u32, &'a u32);
-    type IntoIter = Zip<slice::Iter<'a, u32>, slice::Iter<'a, u32>>;
+impl<'a, S> IntoIterator for BsrRef<'a, S> {
+    type Item = (&'a u32, &'a S);
+    type IntoIter = Zip<slice::Iter<'a, u32>, slice::Iter<'a, S>>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.bases.iter().zip(self.states.iter())
     }
 }
 
-impl<'a> From<&'a BsrVec> for BsrRef<'a> {
-    fn from(vec: &'a BsrVec) -> Self {
+impl<'a, S> From<&'a BsrVec<S>> for BsrRef<'a, S> {
+    fn from(vec: &'a BsrVec<S>) -> Self {
         Self {
             bases: &vec.bases,
             states: &vec.states,
@@ -133,32 +167,100 @@ impl<'a> From<&'a BsrVec> for BsrRef<'a> {
     }
 }
 
+/// Merges pairs of adjacent 32-bit bases into one 64-bit state.
+impl From<&BsrVec<u32>> for BsrVec<u64> {
+    fn from(bsr: &BsrVec<u32>) -> Self {
+        let mut result = BsrVec::with_capacities(bsr.len());
+        for (&base, &state) in bsr.iter() {
+            let state = (state as u64) << ((base & 1) * u32::BITS);
+            if result.bases.last() == Some(&(base >> 1)) {
+                *result.states.last_mut().unwrap() |= state;
+            }
+            else {
+                result.append(base >> 1, state);
+            }
+        }
+        result
+    }
+}
+
+/// Splits each 64-bit state into its non-empty 32-bit halves.
+impl From<&BsrVec<u64>> for BsrVec<u32> {
+    fn from(bsr: &BsrVec<u64>) -> Self {
+        let mut result = BsrVec::with_capacities(bsr.len() * 2);
+        for (&base, &state) in bsr.iter() {
+            for (half, half_state) in [state as u32, (state >> u32::BITS) as u32].into_iter().enumerate() {
+                if half_state != 0 {
+                    result.append(base << 1 | half as u32, half_state);
+                }
+            }
+        }
+        result
+    }
+}
+
 pub const BSR_WIDTH: u32 = u32::BITS;
 pub const BSR_SHIFT: u32 = BSR_WIDTH.trailing_zeros();
 pub const BSR_MASK: u32 = BSR_WIDTH - 1;
 
-impl Set<u32> for BsrVec {
+impl<S: BsrState> Set<u32> for BsrVec<S> {
     fn from_sorted(sorted: &[u32]) -> Self {
         let mut bsr = BsrVec::new();
 
         let mut it = sorted.iter().copied();
         if let Some(first) = it.next() {
-            bsr.bases.push(first >> BSR_SHIFT);
-            bsr.states.push(1 << (first & BSR_MASK));
+            bsr.bases.push(first >> S::SHIFT);
+            bsr.states.push(S::bit(first));
         }
 
         for item in it {
-            let base = item >> BSR_SHIFT;
-            let bit = 1 << (item & BSR_MASK);
+            let base = item >> S::SHIFT;
+            let bit = S::bit(item);
 
             if *bsr.bases.last().unwrap() != base {
                 bsr.bases.push(base);
                 bsr.states.push(bit);
             }
             else {
-                *bsr.states.last_mut().unwrap() |= bit;
+                let last = bsr.states.last_mut().unwrap();
+                *last = *last | bit;
             }
         }
         bsr
     }
 }
+
+impl<S: BsrState> Intersect for BsrVec<S> {
+    fn intersect(&self, other: &Self) -> Self {
+        intersect::run_2set_bsr(self.bsr_ref(), other.bsr_ref(), intersect::branchless_merge_bsr)
+    }
+
+    fn intersect_count(&self, other: &Self) -> usize {
+        let mut counter = Counter::new();
+        intersect::branchless_merge_bsr(self.bsr_ref(), other.bsr_ref(), &mut counter);
+        counter.count()
+    }
+}
+
+impl<S: BsrState> Cardinality for BsrVec<S> {
+    fn cardinality(&self) -> usize {
+        BsrVec::cardinality(self)
+    }
+}
+
+impl<S: BsrState> IterSorted<u32> for BsrVec<S> {
+    fn iter_sorted(&self) -> impl Iterator<Item = u32> + '_ {
+        self.iter().flat_map(|(&base, &state)| {
+            let high = base << S::SHIFT;
+            let mut state = state;
+            std::iter::from_fn(move || {
+                if state == S::zero() {
+                    return None;
+                }
+                let item = high | state.trailing_zeros();
+                state = state & (state - S::one());
+                Some(item)
+            })
+        })
+    }
+}
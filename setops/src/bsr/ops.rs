@@ -0,0 +1,246 @@
+/// Union, difference and symmetric difference of BSR sets. Each is built from
+/// a `MatchStates` kernel which finds, for every base of one set, the state of
+/// the other set at that base. The SIMD kernels compare all pairs of bases in
+/// a block as in `shuffling_*_bsr`.
+
+#[cfg(feature = "simd")]
+use std::simd::{*, cmp::*};
+use super::{BsrRef, BsrVec, BsrState};
+#[cfg(feature = "simd")]
+use crate::instructions::load_unsafe;
+
+/// Ors into `matched[i]` the state of `set_b` with base `set_a.bases[i]`, if
+/// any. `matched` has the length of `set_a`.
+pub type MatchStates = for<'a> fn(set_a: BsrRef<'a>, set_b: BsrRef<'a>, matched: &mut [u32]);
+
+impl<'a, S: BsrState> BsrRef<'a, S> {
+    /// Number of items in the set.
+    pub fn cardinality(&self) -> usize {
+        self.states.iter().map(|s| s.count_ones() as usize).sum()
+    }
+
+    pub fn contains(&self, item: u32) -> bool {
+        match self.bases.binary_search(&(item >> S::SHIFT)) {
+            Ok(i) => self.states[i] & S::bit(item) != S::zero(),
+            Err(_) => false,
+        }
+    }
+}
+
+impl<S: BsrState> BsrVec<S> {
+    pub fn cardinality(&self) -> usize {
+        self.bsr_ref().cardinality()
+    }
+
+    pub fn contains(&self, item: u32) -> bool {
+        self.bsr_ref().contains(item)
+    }
+}
+
+impl<'a> BsrRef<'a> {
+    pub fn union(self, other: BsrRef<'a>) -> BsrVec {
+        self.union_with(other, match_states)
+    }
+
+    pub fn difference(self, other: BsrRef<'a>) -> BsrVec {
+        self.difference_with(other, match_states)
+    }
+
+    pub fn symmetric_difference(self, other: BsrRef<'a>) -> BsrVec {
+        self.symmetric_difference_with(other, match_states)
+    }
+
+    pub fn union_with(self, other: BsrRef<'a>, kernel: MatchStates) -> BsrVec {
+        let matched_a = matched(self, other, kernel);
+        let matched_b = matched(other, self, kernel);
+
+        let states_a = self.states.iter().zip(&matched_a).map(|(a, m)| a | m);
+        let states_b = other.states.iter().zip(&matched_b).map(|(&b, &m)| if m == 0 { b } else { 0 });
+        merge_disjoint(self.bases, states_a, other.bases, states_b)
+    }
+
+    pub fn difference_with(self, other: BsrRef<'a>, kernel: MatchStates) -> BsrVec {
+        let matched_a = matched(self, other, kernel);
+
+        let mut result = BsrVec::with_capacities(self.len());
+        let states = self.states.iter().zip(&matched_a).map(|(a, m)| a & !m);
+        for (&base, state) in self.bases.iter().zip(states) {
+            if state != 0 {
+                result.append(base, state);
+            }
+        }
+        result
+    }
+
+    pub fn symmetric_difference_with(self, other: BsrRef<'a>, kernel: MatchStates) -> BsrVec {
+        let matched_a = matched(self, other, kernel);
+        let matched_b = matched(other, self, kernel);
+
+        let states_a = self.states.iter().zip(&matched_a).map(|(a, m)| a ^ m);
+        let states_b = other.states.iter().zip(&matched_b).map(|(&b, &m)| if m == 0 { b } else { 0 });
+        merge_disjoint(self.bases, states_a, other.bases, states_b)
+    }
+}
+
+impl BsrVec {
+    pub fn union(&self, other: &BsrVec) -> BsrVec {
+        self.bsr_ref().union(other.bsr_ref())
+    }
+
+    pub fn difference(&self, other: &BsrVec) -> BsrVec {
+        self.bsr_ref().difference(other.bsr_ref())
+    }
+
+    pub fn symmetric_difference(&self, other: &BsrVec) -> BsrVec {
+        self.bsr_ref().symmetric_difference(other.bsr_ref())
+    }
+
+    /// Builds from a dense bitmap, where bit `i` of `words[w]` represents the
+    /// item `w * 32 + i`. Each non-zero word becomes a state.
+    pub fn from_bitmap(words: &[u32]) -> Self {
+        let mut bsr = BsrVec::new();
+        for (base, &state) in words.iter().enumerate() {
+            if state != 0 {
+                bsr.append(base as u32, state);
+            }
+        }
+        bsr
+    }
+
+    /// Inverse of `from_bitmap`, with enough words to hold the largest item.
+    pub fn to_bitmap(&self) -> Vec<u32> {
+        let len = self.bases.last().map(|&b| b as usize + 1).unwrap_or(0);
+        let mut words = vec![0; len];
+        for (&base, &state) in self.iter() {
+            words[base as usize] = state;
+        }
+        words
+    }
+}
+
+/// Builds from items in any order, possibly with duplicates.
+impl FromIterator<u32> for BsrVec {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        let mut items: Vec<u32> = iter.into_iter().collect();
+        items.sort_unstable();
+        <BsrVec as crate::Set<u32>>::from_sorted(&items)
+    }
+}
+
+// Chooses the widest kernel available.
+fn match_states<'a>(set_a: BsrRef<'a>, set_b: BsrRef<'a>, matched: &mut [u32]) {
+    #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+    return match_states_avx512(set_a, set_b, matched);
+    #[cfg(all(feature = "simd", target_feature = "avx2", not(target_feature = "avx512f")))]
+    return match_states_avx2(set_a, set_b, matched);
+    #[cfg(all(feature = "simd", target_feature = "ssse3", not(target_feature = "avx2")))]
+    return match_states_sse(set_a, set_b, matched);
+    #[allow(unreachable_code)]
+    match_states_scalar(set_a, set_b, matched)
+}
+
+fn matched<'a>(set_a: BsrRef<'a>, set_b: BsrRef<'a>, kernel: MatchStates) -> Vec<u32> {
+    let mut matched = vec![0; set_a.len()];
+    kernel(set_a, set_b, &mut matched);
+    matched
+}
+
+// Merges two sets with no bases in common, skipping zero states.
+fn merge_disjoint(
+    bases_a: &[u32],
+    states_a: impl Iterator<Item=u32>,
+    bases_b: &[u32],
+    states_b: impl Iterator<Item=u32>) -> BsrVec
+{
+    let mut result = BsrVec::with_capacities(bases_a.len() + bases_b.len());
+    let mut it_a = bases_a.iter().copied().zip(states_a).filter(|&(_, s)| s != 0).peekable();
+    let mut it_b = bases_b.iter().copied().zip(states_b).filter(|&(_, s)| s != 0).peekable();
+
+    loop {
+        let next = match (it_a.peek(), it_b.peek()) {
+            (Some(&(base_a, _)), Some(&(base_b, _))) => {
+                debug_assert!(base_a != base_b);
+                if base_a < base_b { it_a.next() } else { it_b.next() }
+            },
+            (Some(_), None) => it_a.next(),
+            (None, Some(_)) => it_b.next(),
+            (None, None) => break,
+        };
+        let (base, state) = next.unwrap();
+        result.append(base, state);
+    }
+    result
+}
+
+pub fn match_states_scalar<'a>(set_a: BsrRef<'a>, set_b: BsrRef<'a>, matched: &mut [u32]) {
+    debug_assert!(matched.len() == set_a.len());
+
+    let mut idx_a = 0;
+    let mut idx_b = 0;
+    while idx_a < set_a.len() && idx_b < set_b.len() {
+        let base_a = set_a.bases[idx_a];
+        let base_b = set_b.bases[idx_b];
+        if base_a == base_b {
+            matched[idx_a] |= set_b.states[idx_b];
+        }
+        idx_a += (base_a <= base_b) as usize;
+        idx_b += (base_b <= base_a) as usize;
+    }
+}
+
+#[cfg(all(feature = "simd", target_feature = "ssse3"))]
+pub fn match_states_sse<'a>(set_a: BsrRef<'a>, set_b: BsrRef<'a>, matched: &mut [u32]) {
+    match_states_simd::<4>(set_a, set_b, matched)
+}
+
+#[cfg(all(feature = "simd", target_feature = "avx2"))]
+pub fn match_states_avx2<'a>(set_a: BsrRef<'a>, set_b: BsrRef<'a>, matched: &mut [u32]) {
+    match_states_simd::<8>(set_a, set_b, matched)
+}
+
+#[cfg(all(feature = "simd", target_feature = "avx512f"))]
+pub fn match_states_avx512<'a>(set_a: BsrRef<'a>, set_b: BsrRef<'a>, matched: &mut [u32]) {
+    match_states_simd::<16>(set_a, set_b, matched)
+}
+
+// Compares each block of W bases in A with the W rotations of a block of B.
+// A block may overlap several blocks of B, so matches are accumulated in
+// `matched` rather than emitted per block.
+#[cfg(feature = "simd")]
+#[inline]
+fn match_states_simd<'a, const W: usize>(set_a: BsrRef<'a>, set_b: BsrRef<'a>, matched: &mut [u32])
+where
+    LaneCount<W>: SupportedLaneCount,
+{
+    debug_assert!(matched.len() == set_a.len());
+    let st_a = (set_a.len() / W) * W;
+    let st_b = (set_b.len() / W) * W;
+
+    let mut i_a: usize = 0;
+    let mut i_b: usize = 0;
+    while i_a < st_a && i_b < st_b {
+        let base_a: Simd<u32, W> = unsafe { load_unsafe(set_a.bases.as_ptr().add(i_a)) };
+        let mut base_b: Simd<u32, W> = unsafe { load_unsafe(set_b.bases.as_ptr().add(i_b)) };
+        let mut state_b: Simd<u32, W> = unsafe { load_unsafe(set_b.states.as_ptr().add(i_b)) };
+
+        let mut state_all = Simd::<u32, W>::splat(0);
+        for _ in 0..W {
+            state_all |= base_a.simd_eq(base_b).select(state_b, Simd::splat(0));
+            base_b = base_b.rotate_elements_left::<1>();
+            state_b = state_b.rotate_elements_left::<1>();
+        }
+
+        let out = &mut matched[i_a..i_a + W];
+        (Simd::<u32, W>::from_slice(out) | state_all).copy_to_slice(out);
+
+        let a_max = unsafe { *set_a.bases.get_unchecked(i_a + W - 1) };
+        let b_max = unsafe { *set_b.bases.get_unchecked(i_b + W - 1) };
+
+        i_a += W * (a_max <= b_max) as usize;
+        i_b += W * (b_max <= a_max) as usize;
+    }
+    match_states_scalar(
+        set_a.advanced_by(i_a),
+        set_b.advanced_by(i_b),
+        &mut matched[i_a..])
+}
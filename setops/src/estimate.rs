@@ -0,0 +1,181 @@
+/// Cheap intersection size estimates, e.g., for choosing an algorithm or a
+/// k-set intersection order before doing any work. Each estimate is returned
+/// with its approximate standard error.
+
+use std::collections::BinaryHeap;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Estimate {
+    pub value: f64,
+    pub std_error: f64,
+}
+
+impl Estimate {
+    pub fn exact(value: usize) -> Self {
+        Self { value: value as f64, std_error: 0.0 }
+    }
+}
+
+/// Estimates |small ∩ large| by looking up `samples` evenly spaced items of
+/// `small` in `large` with binary search, costing O(samples * log |large|).
+///
+/// The hit rate p is estimated from a sample of n of the N items in `small`,
+/// so the standard error is `N * sqrt(p(1-p)/n * (N-n)/(N-1))`, i.e., at most
+/// `N / (2 sqrt(n))`. The estimate is exact if `samples >= small.len()`.
+pub fn sample_estimate<T: Ord>(small: &[T], large: &[T], samples: usize) -> Estimate {
+    let n = small.len();
+    if samples >= n {
+        let exact = small.iter().filter(|&item| large.binary_search(item).is_ok()).count();
+        return Estimate::exact(exact);
+    }
+    if samples == 0 {
+        return Estimate { value: 0.0, std_error: n as f64 };
+    }
+
+    let stride = n as f64 / samples as f64;
+    let hits = (0..samples)
+        .map(|i| &small[(i as f64 * stride) as usize])
+        .filter(|&item| large.binary_search(item).is_ok())
+        .count();
+
+    let (n_f, samples_f) = (n as f64, samples as f64);
+    // Smooth p so that the error is not reported as zero when all or none of
+    // the samples hit.
+    let p = (hits as f64 + 1.0) / (samples_f + 2.0);
+    let finite_population = (n_f - samples_f) / (n_f - 1.0);
+
+    Estimate {
+        value: hits as f64 / samples_f * n_f,
+        std_error: n_f * (p * (1.0 - p) / samples_f * finite_population).sqrt(),
+    }
+}
+
+/// Estimates the number of distinct items hashed into a bitmap of `bits` bits
+/// of which `ones` are set.
+///
+/// Whang, K. Y., Vander-Zanden, B. T., & Taylor, H. M. (1990). A linear-time
+/// probabilistic counting algorithm for database applications. ACM
+/// Transactions on Database Systems (TODS), 15(2), 208-229.
+///
+/// For n items and load t = n/bits the standard error is
+/// `sqrt(bits * (e^t - t - 1))`.
+pub fn linear_counting(ones: usize, bits: usize) -> Estimate {
+    // A full bitmap gives no information beyond a lower bound.
+    let zeros = (bits - ones).max(1) as f64;
+    let m = bits as f64;
+
+    let value = -m * (zeros / m).ln();
+    let t = value / m;
+
+    Estimate {
+        value,
+        std_error: (m * (t.exp() - t - 1.0)).sqrt(),
+    }
+}
+
+/// K minimum values (bottom-k MinHash) sketch of a set. The sketch keeps the
+/// k smallest 64-bit hashes of the set's items along with its exact size and
+/// can be stored alongside any set representation.
+///
+/// Beyer, K., Haas, P. J., Reinwald, B., Sismanis, Y., & Gemulla, R. (2007,
+/// June). On synopses for distinct-value estimation under multiset operations.
+/// In Proceedings of the 2007 ACM SIGMOD international conference on
+/// Management of data (pp. 199-210).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KmvSketch {
+    // Sorted in increasing order.
+    hashes: Vec<u64>,
+    k: usize,
+    len: usize,
+}
+
+impl KmvSketch {
+    pub fn new<T>(set: &[T], k: usize) -> Self
+    where
+        T: Copy + Into<i64>,
+    {
+        assert!(k > 0);
+
+        let mut heap: BinaryHeap<u64> = BinaryHeap::with_capacity(k + 1);
+        for &item in set {
+            let hash = mix64(item.into() as u64);
+            if heap.len() < k {
+                heap.push(hash);
+            }
+            else if hash < *heap.peek().unwrap() {
+                heap.pop();
+                heap.push(hash);
+            }
+        }
+
+        Self {
+            hashes: heap.into_sorted_vec(),
+            k,
+            len: set.len(),
+        }
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Estimates the Jaccard similarity J = |A ∩ B| / |A ∪ B| from the k
+    /// smallest hashes of the union. With k' = min(k_a, k_b), the standard error
+    /// is `sqrt(J(1-J)/k')`. As J is unknown the bound `1/(2 sqrt(k'))` is
+    /// reported. The estimate is exact if both sets have at most k' items.
+    pub fn jaccard(&self, other: &Self) -> Estimate {
+        let k = self.k.min(other.k);
+        let exact = self.len <= k && other.len <= k;
+        let limit = if exact { usize::MAX } else { k };
+
+        let mut i_a = 0;
+        let mut i_b = 0;
+        let mut union = 0;
+        let mut common = 0;
+        while union < limit && (i_a < self.hashes.len() || i_b < other.hashes.len()) {
+            let a = self.hashes.get(i_a).copied().unwrap_or(u64::MAX);
+            let b = other.hashes.get(i_b).copied().unwrap_or(u64::MAX);
+            common += (a == b) as usize;
+            i_a += (a <= b) as usize;
+            i_b += (b <= a) as usize;
+            union += 1;
+        }
+
+        if union == 0 {
+            return Estimate::exact(0);
+        }
+
+        Estimate {
+            value: common as f64 / union as f64,
+            std_error: if exact { 0.0 } else { 0.5 / (union as f64).sqrt() },
+        }
+    }
+
+    /// Estimates |A ∩ B| as `J / (1 + J) * (|A| + |B|)`. The derivative of this
+    /// with respect to J is at most |A| + |B|, which bounds the reported error.
+    pub fn intersection(&self, other: &Self) -> Estimate {
+        let jaccard = self.jaccard(other);
+        let total = (self.len + other.len) as f64;
+        let j = jaccard.value;
+
+        Estimate {
+            value: j / (1.0 + j) * total,
+            std_error: total * jaccard.std_error,
+        }
+    }
+}
+
+// splitmix64 finalizer
+fn mix64(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
@@ -0,0 +1,324 @@
+/// Graph analytics built on set intersection. Neighbour lists are stored in
+/// compressed sparse row (CSR) form so that each list is a sorted slice which
+/// can be passed directly to any 2-set intersection algorithm.
+///
+/// Triangle counting and k-clique listing orient the graph by degree first,
+/// as described in
+///
+/// Danisch, M., Balalau, O., & Sozio, M. (2018, April). Listing k-cliques in
+/// sparse real-world graphs. In Proceedings of the 2018 World Wide Web
+/// Conference (pp. 589-598).
+
+use std::io::{self, BufRead};
+use crate::{
+    bsr::{BsrVec, Intersect2BsrV},
+    intersect::Intersect2,
+    visitor::{Counter, VecWriter, Clearable},
+    Set,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsrGraph {
+    offsets: Vec<usize>,
+    neighbours: Vec<u32>,
+}
+
+impl CsrGraph {
+    /// Builds an undirected graph. Each edge is added in both directions,
+    /// self-loops are dropped and duplicate edges are merged.
+    pub fn from_edges<I>(edges: I) -> Self
+    where
+        I: IntoIterator<Item=(u32, u32)>,
+    {
+        let mut directed = Vec::new();
+        for (from, to) in edges {
+            if from != to {
+                directed.push((from, to));
+                directed.push((to, from));
+            }
+        }
+        Self::from_directed_edges(directed)
+    }
+
+    /// Builds a directed graph. Each neighbour list is sorted and deduplicated.
+    pub fn from_directed_edges(mut edges: Vec<(u32, u32)>) -> Self {
+        edges.sort_unstable();
+        edges.dedup();
+
+        let vertex_count = edges.iter()
+            .map(|&(from, to)| from.max(to) as usize + 1)
+            .max()
+            .unwrap_or(0);
+
+        let mut offsets = vec![0; vertex_count + 1];
+        for &(from, _) in &edges {
+            offsets[from as usize + 1] += 1;
+        }
+        for i in 0..vertex_count {
+            offsets[i + 1] += offsets[i];
+        }
+
+        let neighbours = edges.into_iter().map(|(_, to)| to).collect();
+        Self { offsets, neighbours }
+    }
+
+    /// Reads a whitespace-separated edge list as distributed by SNAP. Lines
+    /// starting with `#` or `%` are treated as comments.
+    pub fn from_edge_list<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut edges = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with('%') {
+                continue;
+            }
+
+            let mut fields = line.split_whitespace().map(|f| f.parse::<u32>());
+            match (fields.next(), fields.next()) {
+                (Some(Ok(from)), Some(Ok(to))) => edges.push((from, to)),
+                _ => return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid edge {:?}", line))),
+            }
+        }
+        Ok(Self::from_edges(edges))
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Number of directed edges, i.e., twice the number of undirected edges.
+    pub fn edge_count(&self) -> usize {
+        self.neighbours.len()
+    }
+
+    pub fn degree(&self, vertex: u32) -> usize {
+        let v = vertex as usize;
+        self.offsets[v + 1] - self.offsets[v]
+    }
+
+    pub fn neighbours(&self, vertex: u32) -> &[u32] {
+        let v = vertex as usize;
+        &self.neighbours[self.offsets[v]..self.offsets[v + 1]]
+    }
+
+    /// Relabels vertices in increasing order of degree and keeps only edges
+    /// from lower to higher rank. The result is acyclic, each triangle or
+    /// clique is reachable from exactly one vertex, and out-degrees are small.
+    pub fn degree_ordered(&self) -> Self {
+        let mut order: Vec<u32> = (0..self.vertex_count() as u32).collect();
+        order.sort_by_key(|&v| (self.degree(v), v));
+
+        let mut rank = vec![0; order.len()];
+        for (r, &v) in order.iter().enumerate() {
+            rank[v as usize] = r as u32;
+        }
+
+        let mut offsets = Vec::with_capacity(self.offsets.len());
+        let mut neighbours = Vec::with_capacity(self.neighbours.len() / 2);
+        offsets.push(0);
+        for (r, &v) in order.iter().enumerate() {
+            let start = neighbours.len();
+            neighbours.extend(self.neighbours(v).iter()
+                .map(|&u| rank[u as usize])
+                .filter(|&u| u > r as u32));
+            neighbours[start..].sort_unstable();
+            offsets.push(neighbours.len());
+        }
+
+        Self { offsets, neighbours }
+    }
+
+    pub fn to_bsr(&self) -> Vec<BsrVec> {
+        (0..self.vertex_count() as u32)
+            .map(|v| BsrVec::from_sorted(self.neighbours(v)))
+            .collect()
+    }
+}
+
+/// Counts triangles in a degree-ordered graph (see `CsrGraph::degree_ordered`).
+pub fn triangle_count(graph: &CsrGraph, intersect: Intersect2<[u32], Counter>) -> usize {
+    (0..graph.vertex_count() as u32)
+        .map(|v| vertex_triangles(graph, v, intersect))
+        .sum()
+}
+
+/// As `triangle_count` but intersects neighbourhoods in BSR form, where `bsr`
+/// is the result of `graph.to_bsr()`.
+pub fn triangle_count_bsr(
+    graph: &CsrGraph,
+    bsr: &[BsrVec],
+    intersect: Intersect2BsrV<Counter>) -> usize
+{
+    debug_assert!(bsr.len() == graph.vertex_count());
+
+    (0..graph.vertex_count() as u32)
+        .map(|v| vertex_triangles_bsr(graph, bsr, v, intersect))
+        .sum()
+}
+
+/// Counts triangles using `threads` worker threads. Vertices are interleaved
+/// between threads as high-degree vertices are clustered after ordering.
+pub fn triangle_count_par(
+    graph: &CsrGraph,
+    intersect: Intersect2<[u32], Counter>,
+    threads: usize) -> usize
+{
+    par_vertex_sum(graph.vertex_count(), threads,
+        |v| vertex_triangles(graph, v, intersect))
+}
+
+pub fn triangle_count_bsr_par(
+    graph: &CsrGraph,
+    bsr: &[BsrVec],
+    intersect: Intersect2BsrV<Counter>,
+    threads: usize) -> usize
+{
+    debug_assert!(bsr.len() == graph.vertex_count());
+
+    par_vertex_sum(graph.vertex_count(), threads,
+        |v| vertex_triangles_bsr(graph, bsr, v, intersect))
+}
+
+/// Lists all k-cliques of a degree-ordered graph, passing the vertices of each
+/// to `visit` in increasing order.
+pub fn k_cliques<F>(
+    graph: &CsrGraph,
+    k: usize,
+    intersect: Intersect2<[u32], VecWriter<u32>>,
+    mut visit: F)
+where
+    F: FnMut(&[u32]),
+{
+    let mut buffers: Vec<VecWriter<u32>> = (0..k).map(|_| VecWriter::new()).collect();
+    let mut clique = Vec::with_capacity(k);
+
+    for v in 0..graph.vertex_count() as u32 {
+        vertex_cliques(graph, k, v, intersect, &mut clique, &mut buffers, &mut visit);
+    }
+}
+
+pub fn k_clique_count(
+    graph: &CsrGraph,
+    k: usize,
+    intersect: Intersect2<[u32], VecWriter<u32>>) -> usize
+{
+    let mut count = 0;
+    k_cliques(graph, k, intersect, |_| count += 1);
+    count
+}
+
+pub fn k_clique_count_par(
+    graph: &CsrGraph,
+    k: usize,
+    intersect: Intersect2<[u32], VecWriter<u32>>,
+    threads: usize) -> usize
+{
+    par_vertex_sum(graph.vertex_count(), threads, |v| {
+        let mut buffers: Vec<VecWriter<u32>> = (0..k).map(|_| VecWriter::new()).collect();
+        let mut clique = Vec::with_capacity(k);
+        let mut count = 0;
+        vertex_cliques(graph, k, v, intersect,
+            &mut clique, &mut buffers, &mut |_: &[u32]| count += 1);
+        count
+    })
+}
+
+fn vertex_triangles(graph: &CsrGraph, v: u32, intersect: Intersect2<[u32], Counter>) -> usize {
+    let out = graph.neighbours(v);
+    out.iter()
+        .map(|&u| {
+            let mut counter = Counter::new();
+            intersect(out, graph.neighbours(u), &mut counter);
+            counter.count()
+        })
+        .sum()
+}
+
+fn vertex_triangles_bsr(
+    graph: &CsrGraph,
+    bsr: &[BsrVec],
+    v: u32,
+    intersect: Intersect2BsrV<Counter>) -> usize
+{
+    let out = bsr[v as usize].bsr_ref();
+    graph.neighbours(v).iter()
+        .map(|&u| {
+            let mut counter = Counter::new();
+            intersect(out, bsr[u as usize].bsr_ref(), &mut counter);
+            counter.count()
+        })
+        .sum()
+}
+
+fn vertex_cliques<F>(
+    graph: &CsrGraph,
+    k: usize,
+    v: u32,
+    intersect: Intersect2<[u32], VecWriter<u32>>,
+    clique: &mut Vec<u32>,
+    buffers: &mut [VecWriter<u32>],
+    visit: &mut F)
+where
+    F: FnMut(&[u32]),
+{
+    if k == 0 {
+        return;
+    }
+    clique.clear();
+    clique.push(v);
+    extend_clique(graph, k, graph.neighbours(v), intersect, clique, buffers, visit);
+}
+
+fn extend_clique<F>(
+    graph: &CsrGraph,
+    k: usize,
+    candidates: &[u32],
+    intersect: Intersect2<[u32], VecWriter<u32>>,
+    clique: &mut Vec<u32>,
+    buffers: &mut [VecWriter<u32>],
+    visit: &mut F)
+where
+    F: FnMut(&[u32]),
+{
+    if clique.len() == k {
+        visit(clique);
+        return;
+    }
+    if clique.len() + candidates.len() < k {
+        return;
+    }
+
+    let (next, rest) = buffers.split_first_mut().unwrap();
+    for &u in candidates {
+        next.clear();
+        intersect(candidates, graph.neighbours(u), next);
+
+        clique.push(u);
+        extend_clique(graph, k, next.as_ref(), intersect, clique, rest, visit);
+        clique.pop();
+    }
+}
+
+fn par_vertex_sum<F>(vertex_count: usize, threads: usize, count: F) -> usize
+where
+    F: Fn(u32) -> usize + Sync,
+{
+    let threads = threads.max(1);
+    let count = &count;
+
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|t| scope.spawn(move ||
+                (t..vertex_count).step_by(threads)
+                    .map(|v| count(v as u32))
+                    .sum::<usize>()
+            ))
+            .collect();
+
+        workers.into_iter()
+            .map(|w| w.join().unwrap())
+            .sum()
+    })
+}
@@ -11,8 +11,15 @@ mod bmiss;
 mod qfilter;
 mod qfilter_c;
 mod avx512;
+mod mixed;
+mod hash_set;
+mod context;
+mod inplace;
+mod multiset;
+mod keyed;
 pub mod mono;
 pub mod fesia;
+pub mod probe;
 
 pub use {
     merge::*,
@@ -21,6 +28,10 @@ pub use {
     std_set::*,
     svs::*,
     bmiss::*,
+    mixed::*,
+    context::*,
+    multiset::*,
+    keyed::*,
 };
 
 #[cfg(all(feature = "simd", target_feature = "ssse3"))]
@@ -29,18 +40,29 @@ pub use {
     broadcast::*,
     simd_galloping::*,
     qfilter::*,
-    qfilter_c::qfilter_c,
+    qfilter_c::{qfilter_c, QFilterItem},
     lbk::*,
 };
 #[cfg(all(feature = "simd", target_feature = "avx512f"))]
 pub use avx512::*;
+#[cfg(feature = "simd")]
+pub use hash_set::*;
 
-use crate::{visitor::VecWriter, bsr::{BsrVec, BsrRef}};
+use crate::{visitor::VecWriter, bsr::{BsrVec, BsrRef, BsrState}};
 
 pub type Intersect2<I, V> = fn(a: &I, b: &I, visitor: &mut V);
+/// Writes matches to `result`, which must hold the smaller set plus
+/// `RESULT_SLACK` items. Returns the number of matches.
 pub type Intersect2C<I> = fn(a: &I, b: &I, result: &mut I) -> usize;
+/// Intersects `b` into `a`, compacting matches to its front. Returns the
+/// number of matches.
+pub type Intersect2InPlace<T> = fn(a: &mut [T], b: &[T]) -> usize;
 pub type IntersectK<S, V> = fn(sets: &[S], visitor: &mut V);
 
+/// Spare items an `Intersect2C` result needs past the size of the smaller set,
+/// as kernels may store a whole vector past the last match.
+pub const RESULT_SLACK: usize = 3;
+
 pub fn run_2set<T>(
     set_a: &[T],
     set_b: &[T],
@@ -58,7 +80,7 @@ pub fn run_2set_c<T>(
 where
     T: Default + Clone + Copy
 {
-    let len = set_a.len().min(set_b.len());
+    let len = set_a.len().min(set_b.len()) + RESULT_SLACK;
     let mut result = vec![T::default();len];
 
     let result_len = intersect(set_a, set_b, &mut result);
@@ -79,10 +101,10 @@ where
     writer.into()
 }
 
-pub fn run_2set_bsr<'a>(
-    set_a: BsrRef<'a>,
-    set_b: BsrRef<'a>,
-    intersect: fn(l: BsrRef<'a>, r: BsrRef<'a>, v: &mut BsrVec)) -> BsrVec
+pub fn run_2set_bsr<'a, S: BsrState>(
+    set_a: BsrRef<'a, S>,
+    set_b: BsrRef<'a, S>,
+    intersect: fn(l: BsrRef<'a, S>, r: BsrRef<'a, S>, v: &mut BsrVec<S>)) -> BsrVec<S>
 {
     let mut writer = BsrVec::new();
     intersect(set_a, set_b, &mut writer);
@@ -9,7 +9,8 @@ use smallvec::{SmallVec, smallvec};
 
 use crate::{
     intersect::galloping::binary_search,
-    visitor::Visitor,
+    visitor::{Visitor, BsrVisitor},
+    bsr::BsrRef,
 };
 
 /// Recursively intersects the two sets.
@@ -80,10 +81,8 @@ where
         let large_set = large_set.as_ref();
         let large_partition = binary_search(large_set, target, 0, large_set.len() as isize - 1);
 
-        if large_partition >= large_set.len() {
-            return;
-        }
-        if large_set[large_partition] == target {
+        // Past the end, only the lower parts can still match.
+        if large_partition < large_set.len() && large_set[large_partition] == target {
             match_count += 1;
         }
 
@@ -91,11 +90,11 @@ where
         uppers.push(&large_set[large_partition..]);
     }
 
+    // Visits in order: items below the target, the target, then above it.
+    baezayates_k(&lowers, visitor);
     if match_count == sets.len() - 1 {
         visitor.visit(target);
     }
-
-    baezayates_k(&lowers, visitor);
     baezayates_k(&uppers, visitor);
 }
 
@@ -117,7 +116,23 @@ where
 
     // TODO: check if this optimisation is meaningful
     let mut positions_vec: SmallVec<[usize; 8]> = smallvec![0; sets.len()];
-    let positions = &mut positions_vec[..];
+    small_adaptive_with_positions(sets, &mut positions_vec, visitor);
+}
+
+/// As `small_adaptive`, using `positions` as scratch instead of allocating
+/// when there are more than 8 sets. `positions` must hold one item per set.
+pub fn small_adaptive_with_positions<T, S, V>(
+    sets: &[S],
+    positions: &mut [usize],
+    visitor: &mut V)
+where
+    T: Ord + Copy,
+    S: AsRef<[T]>,
+    V: Visitor<T>,
+{
+    assert!(sets.len() >= 2);
+    let positions = &mut positions[..sets.len()];
+    positions.fill(0);
 
     'outer: for &element in sets[0].as_ref() {
 
@@ -146,6 +161,47 @@ where
     }
 }
 
+/// As `small_adaptive` for BSR sets, galloping over bases as in
+/// `galloping_bsr`. Assumes input sets are ordered from smallest to largest.
+pub fn small_adaptive_bsr<'a, V>(sets: &[BsrRef<'a>], visitor: &mut V)
+where
+    V: BsrVisitor,
+{
+    assert!(sets.len() >= 2);
+
+    let mut positions_vec: SmallVec<[usize; 8]> = smallvec![0; sets.len()];
+    let positions = &mut positions_vec[..];
+
+    'outer: for (&base, &state) in sets[0] {
+        let mut state = state;
+
+        for (i, set) in sets.iter().enumerate().skip(1) {
+            let pos = positions[i];
+            let mut offset = 1;
+
+            while pos + offset < set.len() && set.bases[pos + offset] <= base {
+                offset *= 2;
+            }
+
+            let lo = pos as isize;
+            let hi = (set.len() as isize - 1).min((pos + offset) as isize);
+
+            let new_pos = binary_search(set.bases, base, lo, hi);
+
+            positions[i] = new_pos;
+
+            if new_pos >= set.len() || set.bases[new_pos] != base {
+                continue 'outer;
+            }
+            state &= set.states[new_pos];
+            if state == 0 {
+                continue 'outer;
+            }
+        }
+        visitor.visit_bsr(base, state);
+    }
+}
+
 // Experiment: sort sets each iteration. Result: always slower than standard Small Adaptive.
 pub fn small_adaptive_sorted<T, S, V>(given_sets: &[S], visitor: &mut V)
 where
@@ -0,0 +1,141 @@
+/// Scratch space for running many intersections without allocating. Buffers
+/// grow to the largest query seen and are then reused, so a context kept per
+/// worker thread reaches a steady state with no allocation.
+
+use crate::visitor::{VecWriter, Clearable};
+use super::{
+    svs_generic, svs_generic_c, svs_generic_inplace, small_adaptive_with_positions,
+    Intersect2, Intersect2C, Intersect2InPlace, IntersectK, RESULT_SLACK,
+};
+
+/// Buffers passed to kernels start on a cache line boundary.
+const ALIGN: usize = 64;
+
+pub struct IntersectionContext<T> {
+    left: VecWriter<T>,
+    right: VecWriter<T>,
+    left_c: ScratchBuffer<T>,
+    right_c: ScratchBuffer<T>,
+    positions: Vec<usize>,
+}
+
+impl<T> IntersectionContext<T> {
+    pub fn new() -> Self {
+        Self {
+            left: VecWriter::new(),
+            right: VecWriter::new(),
+            left_c: ScratchBuffer::new(),
+            right_c: ScratchBuffer::new(),
+            positions: Vec::new(),
+        }
+    }
+
+    pub fn run_2set(
+        &mut self,
+        set_a: &[T],
+        set_b: &[T],
+        intersect: Intersect2<[T], VecWriter<T>>) -> &[T]
+    {
+        self.left.clear();
+        intersect(set_a, set_b, &mut self.left);
+        self.left.as_ref()
+    }
+
+    /// Kernels which allocate their own scratch, e.g., `small_adaptive` for
+    /// more than 8 sets, still allocate here. See `run_small_adaptive`.
+    pub fn run_kset<S>(&mut self, sets: &[S], intersect: IntersectK<S, VecWriter<T>>) -> &[T]
+    where
+        S: AsRef<[T]>,
+    {
+        assert!(sets.len() >= 2);
+
+        self.left.clear();
+        intersect(sets, &mut self.left);
+        self.left.as_ref()
+    }
+
+    /// As `small_adaptive`, keeping its positions in the context.
+    pub fn run_small_adaptive<S>(&mut self, sets: &[S]) -> &[T]
+    where
+        T: Ord + Copy,
+        S: AsRef<[T]>,
+    {
+        if self.positions.len() < sets.len() {
+            self.positions.resize(sets.len(), 0);
+        }
+        self.left.clear();
+        small_adaptive_with_positions(sets, &mut self.positions, &mut self.left);
+        self.left.as_ref()
+    }
+
+    /// As `run_svs`, alternating between the context's two writers.
+    /// Assumes sets are ordered from smallest to largest.
+    pub fn run_svs<S>(&mut self, sets: &[S], intersect: Intersect2<[T], VecWriter<T>>) -> &[T]
+    where
+        T: Ord + Copy,
+        S: AsRef<[T]>,
+    {
+        self.left.clear();
+        self.right.clear();
+        let result: &VecWriter<T> = svs_generic(sets, &mut self.left, &mut self.right, intersect);
+        result.as_ref()
+    }
+}
+
+impl<T: Default + Copy> IntersectionContext<T> {
+    /// As `svs_generic_c`, with both buffers sized to the largest set plus
+    /// `RESULT_SLACK`.
+    pub fn run_svs_c<S>(&mut self, sets: &[S], intersect: Intersect2C<[T]>) -> &[T]
+    where
+        T: Ord,
+        S: AsRef<[T]>,
+    {
+        let capacity = sets.iter().map(|s| s.as_ref().len()).max().unwrap_or(0)
+            + RESULT_SLACK;
+        let left = self.left_c.get(capacity);
+        let right = self.right_c.get(capacity);
+        svs_generic_c(sets, left, right, intersect)
+    }
+
+    /// As `svs_generic_inplace`, needing a single buffer.
+    /// Assumes sets are ordered from smallest to largest.
+    pub fn run_svs_inplace<S>(&mut self, sets: &[S], intersect: Intersect2InPlace<T>) -> &[T]
+    where
+        S: AsRef<[T]>,
+    {
+        assert!(sets.len() >= 2);
+
+        let result = self.left_c.get(sets[0].as_ref().len());
+        let count = svs_generic_inplace(sets, result, intersect);
+        &result[..count]
+    }
+}
+
+impl<T> Default for IntersectionContext<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// A vector which is only ever grown. Slices handed out start at an `ALIGN`
+// boundary within it, so it is over-allocated by up to `ALIGN` bytes.
+struct ScratchBuffer<T> {
+    data: Vec<T>,
+}
+
+impl<T> ScratchBuffer<T> {
+    fn new() -> Self {
+        Self { data: Vec::new() }
+    }
+}
+
+impl<T: Default + Copy> ScratchBuffer<T> {
+    fn get(&mut self, len: usize) -> &mut [T] {
+        let slack = ALIGN / std::mem::size_of::<T>().max(1);
+        if self.data.len() < len + slack {
+            self.data = vec![T::default(); len + slack];
+        }
+        let offset = self.data.as_ptr().align_offset(ALIGN).min(slack);
+        &mut self.data[offset..offset + len]
+    }
+}
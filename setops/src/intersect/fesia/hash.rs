@@ -0,0 +1,210 @@
+/// Hash functions mapping items to bitmap positions. Each family provides a
+/// scalar `hash` used for probes and a batch `hash_batch` used when building,
+/// which is vectorised where the hash is expressible with SIMD arithmetic.
+
+use std::{
+    num::Wrapping,
+    simd::*,
+    simd::num::*,
+};
+
+// Items hashed per SIMD batch.
+const BATCH_LANES: usize = 16;
+
+pub trait IntegerHash {
+    /// Identifies the hash function in persisted sets. Must be unique.
+    const ID: u32;
+
+    /// If set, the high bits of the hash are used instead of the low bits.
+    /// For a hash which is monotonic as an unsigned integer, segments are then
+    /// in increasing order and intersections are visited in sorted order.
+    const ORDERED: bool = false;
+
+    fn hash(item: i32) -> i32;
+
+    /// Hashes each item of `items` into `out`, which must have the same length.
+    fn hash_batch(items: &[i32], out: &mut [i32]) {
+        debug_assert!(items.len() == out.len());
+        for (&item, hash) in items.iter().zip(out) {
+            *hash = Self::hash(item);
+        }
+    }
+}
+
+pub(super) fn masked_hash<H: IntegerHash>(item: i32, hash_size: usize) -> i32 {
+    mask_hash::<H>(H::hash(item), hash_size)
+}
+
+/// Reduces a full hash to `hash_size` bits of output space.
+pub(super) fn mask_hash<H: IntegerHash>(hash: i32, hash_size: usize) -> i32 {
+    debug_assert!(hash_size.count_ones() == 1);
+    if H::ORDERED {
+        let shift = u32::BITS - hash_size.trailing_zeros();
+        ((hash as u32) >> shift) as i32
+    }
+    else {
+        hash & (hash_size as i32 - 1)
+    }
+}
+
+pub struct IdentityHash;
+impl IntegerHash for IdentityHash {
+    const ID: u32 = 0;
+
+    fn hash(item: i32) -> i32 {
+        item
+    }
+
+    fn hash_batch(items: &[i32], out: &mut [i32]) {
+        out.copy_from_slice(items);
+    }
+}
+
+/// Order-preserving hash: maps i32 onto u32 in order, so that the high bits
+/// select the segment. Skewed data leads to large segments, falling back to
+/// scalar merges.
+pub struct OrderedHash;
+impl IntegerHash for OrderedHash {
+    const ID: u32 = 2;
+    const ORDERED: bool = true;
+
+    fn hash(item: i32) -> i32 {
+        item ^ i32::MIN
+    }
+}
+
+pub struct MixHash;
+impl IntegerHash for MixHash {
+    const ID: u32 = 1;
+
+    // https://gist.github.com/badboy/6267743
+    fn hash(item: i32) -> i32 {
+        let mut key = Wrapping(item as i32);
+        key = !key + (key << 15); // key = (key << 15) - key - 1;
+        key = key ^ (key >> 12);
+        key = key + (key << 2);
+        key = key ^ (key >> 4);
+        key = key * Wrapping(2057); // key = (key + (key << 3)) + (key << 11);
+        key = key ^ (key >> 16);
+        key.0 as i32
+    }
+
+    fn hash_batch(items: &[i32], out: &mut [i32]) {
+        hash_batch_simd(items, out, Self::hash, |mut key| {
+            let splat = Simd::<i32, BATCH_LANES>::splat;
+            key = !key + (key << splat(15));
+            key ^= key >> splat(12);
+            key += key << splat(2);
+            key ^= key >> splat(4);
+            key *= splat(2057);
+            key ^ (key >> splat(16))
+        });
+    }
+}
+
+/// Multiply-shift hashing, taking the high half of a 64-bit product.
+///
+/// Dietzfelbinger, M., Hagerup, T., Katajainen, J., & Penttonen, M. (1997). A
+/// reliable randomized algorithm for the closest-pair problem. Journal of
+/// Algorithms, 25(1), 19-51.
+pub struct MultiplyShiftHash;
+impl MultiplyShiftHash {
+    const MULTIPLIER: u64 = 0x9e3779b97f4a7c15;
+}
+
+impl IntegerHash for MultiplyShiftHash {
+    const ID: u32 = 3;
+
+    fn hash(item: i32) -> i32 {
+        ((item as u32 as u64).wrapping_mul(Self::MULTIPLIER) >> 32) as i32
+    }
+
+    fn hash_batch(items: &[i32], out: &mut [i32]) {
+        hash_batch_simd(items, out, Self::hash, |items| {
+            let wide: Simd<u64, BATCH_LANES> = items.cast::<u32>().cast();
+            let product = wide * Simd::splat(Self::MULTIPLIER);
+            (product >> Simd::splat(32)).cast::<u32>().cast()
+        });
+    }
+}
+
+/// Hashes with the SSE4.2 CRC32 instruction, which has a throughput of one
+/// per cycle on most x86 processors.
+#[cfg(target_feature = "sse4.2")]
+pub struct Crc32Hash;
+#[cfg(target_feature = "sse4.2")]
+impl IntegerHash for Crc32Hash {
+    const ID: u32 = 4;
+
+    fn hash(item: i32) -> i32 {
+        #[cfg(target_arch = "x86")]
+        use std::arch::x86::_mm_crc32_u32;
+        #[cfg(target_arch = "x86_64")]
+        use std::arch::x86_64::_mm_crc32_u32;
+
+        unsafe { _mm_crc32_u32(u32::MAX, item as u32) as i32 }
+    }
+}
+
+/// Simple tabulation hashing: XOR of one random table entry per input byte.
+///
+/// Pătraşcu, M., & Thorup, M. (2012). The power of simple tabulation hashing.
+/// Journal of the ACM (JACM), 59(3), 1-50.
+pub struct TabulationHash;
+impl TabulationHash {
+    const TABLES: [[u32; 256]; 4] = tabulation_tables();
+}
+
+impl IntegerHash for TabulationHash {
+    const ID: u32 = 5;
+
+    fn hash(item: i32) -> i32 {
+        let bytes = (item as u32).to_le_bytes();
+        let tables = &Self::TABLES;
+        (tables[0][bytes[0] as usize] ^
+         tables[1][bytes[1] as usize] ^
+         tables[2][bytes[2] as usize] ^
+         tables[3][bytes[3] as usize]) as i32
+    }
+}
+
+// Fills the tables from a fixed splitmix64 stream so that hashes are stable
+// across builds, as required for persisted sets.
+const fn tabulation_tables() -> [[u32; 256]; 4] {
+    let mut tables = [[0; 256]; 4];
+    let mut state: u64 = 0x5851f42d4c957f2d;
+
+    let mut t = 0;
+    while t < 4 {
+        let mut i = 0;
+        while i < 256 {
+            state = state.wrapping_add(0x9e3779b97f4a7c15);
+            let mut x = state;
+            x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+            tables[t][i] = (x ^ (x >> 31)) as u32;
+            i += 1;
+        }
+        t += 1;
+    }
+    tables
+}
+
+fn hash_batch_simd<F, G>(items: &[i32], out: &mut [i32], scalar: F, vector: G)
+where
+    F: Fn(i32) -> i32,
+    G: Fn(Simd<i32, BATCH_LANES>) -> Simd<i32, BATCH_LANES>,
+{
+    debug_assert!(items.len() == out.len());
+
+    let mut items_chunks = items.chunks_exact(BATCH_LANES);
+    let mut out_chunks = out.chunks_exact_mut(BATCH_LANES);
+    for (chunk, hashes) in (&mut items_chunks).zip(&mut out_chunks) {
+        vector(Simd::from_slice(chunk)).copy_to_slice(hashes);
+    }
+
+    let remainder = items_chunks.remainder();
+    for (&item, hash) in remainder.iter().zip(out_chunks.into_remainder()) {
+        *hash = scalar(item);
+    }
+}
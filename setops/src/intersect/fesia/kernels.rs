@@ -0,0 +1,119 @@
+/// Segment intersection kernels. Each item of the smaller segment is broadcast
+/// and compared against vectors of the larger segment. Kernels are
+/// instantiated per small segment size so that broadcasts stay in registers;
+/// larger segments use a loop over the small segment instead. Every pair of
+/// items is compared, so callers merge segments beyond `MAX_KERNEL` instead.
+
+use std::simd::{*, cmp::*};
+use crate::{
+    visitor::{SimdVisitor4, SimdVisitor8, SimdVisitor16},
+    instructions::load_unsafe,
+};
+
+/// Intersects `small[..small_size]` with `vectors` vectors of `WIDTH` items
+/// starting at `large`.
+///
+/// # Safety
+/// `small` must be valid for `small_size` reads and `large` for
+/// `vectors * WIDTH` reads.
+#[inline]
+pub unsafe fn intersect<V, const WIDTH: usize>(
+    small: *const i32,
+    large: *const i32,
+    small_size: usize,
+    vectors: usize,
+    visitor: &mut V)
+where
+    V: SimdVisitor4 + SimdVisitor8 + SimdVisitor16,
+    LaneCount<WIDTH>: SupportedLaneCount,
+{
+    macro_rules! dispatch {
+        ($($size:literal)*) => {
+            match small_size {
+                $($size => kernel::<V, WIDTH, $size>(small, large, vectors, visitor),)*
+                _ => kernel_loop::<V, WIDTH>(small, large, small_size, vectors, visitor),
+            }
+        };
+    }
+    debug_assert!(small_size > 0);
+    dispatch!(1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16
+        17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32);
+}
+
+#[inline]
+unsafe fn kernel<V, const WIDTH: usize, const SMALL: usize>(
+    small: *const i32,
+    large: *const i32,
+    vectors: usize,
+    visitor: &mut V)
+where
+    V: SimdVisitor4 + SimdVisitor8 + SimdVisitor16,
+    LaneCount<WIDTH>: SupportedLaneCount,
+{
+    let items: [Simd<i32, WIDTH>; SMALL] =
+        std::array::from_fn(|i| Simd::splat(*small.add(i)));
+
+    if SMALL == 1 {
+        let mut mask = Mask::<i32, WIDTH>::splat(false);
+        for v in 0..vectors {
+            let v_large: Simd<i32, WIDTH> = load_unsafe(large.add(v * WIDTH));
+            mask |= v_large.simd_eq(items[0]);
+        }
+        if mask.any() {
+            visitor.visit(*small);
+        }
+        return;
+    }
+
+    for v in 0..vectors {
+        let v_large: Simd<i32, WIDTH> = load_unsafe(large.add(v * WIDTH));
+        let mask = items.iter()
+            .fold(Mask::splat(false), |mask, &item| mask | v_large.simd_eq(item));
+        visit_vector(visitor, v_large, mask.to_bitmask());
+    }
+}
+
+// Fallback for segments within `MAX_KERNEL` but with no kernel instantiation,
+// e.g., with a large `VECTORS`.
+#[inline(never)]
+unsafe fn kernel_loop<V, const WIDTH: usize>(
+    small: *const i32,
+    large: *const i32,
+    small_size: usize,
+    vectors: usize,
+    visitor: &mut V)
+where
+    V: SimdVisitor4 + SimdVisitor8 + SimdVisitor16,
+    LaneCount<WIDTH>: SupportedLaneCount,
+{
+    for v in 0..vectors {
+        let v_large: Simd<i32, WIDTH> = load_unsafe(large.add(v * WIDTH));
+        let mut mask = Mask::<i32, WIDTH>::splat(false);
+        for i in 0..small_size {
+            mask |= v_large.simd_eq(Simd::splat(*small.add(i)));
+        }
+        visit_vector(visitor, v_large, mask.to_bitmask());
+    }
+}
+
+// Forwards to the visitor method for `WIDTH`, visiting items one at a time for
+// widths without one. The match is resolved at compile time.
+#[inline]
+fn visit_vector<V, const WIDTH: usize>(visitor: &mut V, value: Simd<i32, WIDTH>, mask: u64)
+where
+    V: SimdVisitor4 + SimdVisitor8 + SimdVisitor16,
+    LaneCount<WIDTH>: SupportedLaneCount,
+{
+    match WIDTH {
+        4 => visitor.visit_vector4(i32x4::from_slice(value.as_array()), mask),
+        8 => visitor.visit_vector8(i32x8::from_slice(value.as_array()), mask),
+        16 => visitor.visit_vector16(i32x16::from_slice(value.as_array()), mask),
+        _ => {
+            let mut mask = mask;
+            while mask != 0 {
+                visitor.visit(value[mask.trailing_zeros() as usize]);
+                mask &= mask - 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+/// Union and difference of FESIA sets. With equal hash sizes, segment i of
+/// the result only depends on segment i of each operand, and the bitmaps show
+/// which segments may share items. Sets with different hash sizes fall back
+/// to merging or probing.
+
+use std::{
+    marker::PhantomData,
+    simd::*,
+    simd::cmp::*,
+    ops::BitAnd,
+};
+use crate::visitor::{VecWriter, Clearable};
+use super::*;
+
+impl<'a, H, S, const LANES: usize> FesiaRef<'a, H, S, LANES>
+where
+    H: IntegerHash,
+    S: SimdElement + MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+    Simd<S, LANES>: BitAnd<Output=Simd<S, LANES>> + SimdPartialEq<Mask=Mask<S, LANES>>,
+{
+    /// Returns A ∪ B with the larger of the two hash sizes.
+    pub fn union(&self, other: &Self) -> Fesia<H, S, LANES> {
+        if self.hash_size != other.hash_size {
+            let hash_size = self.hash_size.max(other.hash_size);
+            return Fesia::with_hash_size(&self.union_sorted(other), hash_size);
+        }
+
+        let bitmap = self.bitmap.iter().zip(other.bitmap)
+            .map(|(a, b)| a | b)
+            .collect();
+
+        self.build_segments(bitmap, |segment, reordered_set| {
+            union_merge(self.segment(segment), other.segment(segment), reordered_set)
+        })
+    }
+
+    /// Returns A \ B with the hash size of A. Segments whose bitmaps overlap
+    /// are intersected with the kernels of `I` to find the items to drop.
+    pub fn difference<I: SegmentIntersect>(&self, other: &Self) -> Fesia<H, S, LANES> {
+        if self.hash_size != other.hash_size {
+            let mut sorted = self.to_sorted_set();
+            sorted.retain(|&item| !other.contains(item));
+            return Fesia::with_hash_size(&sorted, self.hash_size);
+        }
+
+        let segment_bytes = std::mem::size_of::<S>();
+        let mut bitmap = self.bitmap.to_vec();
+        let mut common: VecWriter<i32> = VecWriter::new();
+
+        let mut result = self.build_segments(Vec::new(), |segment, reordered_set| {
+            let bytes = segment * segment_bytes..(segment + 1) * segment_bytes;
+            let overlaps = self.bitmap[bytes.clone()].iter().zip(&other.bitmap[bytes.clone()])
+                .any(|(a, b)| a & b != 0);
+
+            let items = self.segment(segment);
+            if !overlaps {
+                reordered_set.extend_from_slice(items);
+                return;
+            }
+
+            common.clear();
+            I::intersect(
+                self.kernel_input(segment, self.reordered_set.len()),
+                other.kernel_input(segment, other.reordered_set.len()),
+                items.len(),
+                other.segment(segment).len(),
+                &mut common);
+
+            bitmap[bytes].fill(0);
+            for &item in items {
+                if !common.as_ref().contains(&item) {
+                    reordered_set.push(item);
+                    let hash = masked_hash::<H>(item, self.hash_size) as usize;
+                    bitmap[hash / u8::BITS as usize] |= 1 << (hash % u8::BITS as usize);
+                }
+            }
+        });
+        result.bitmap = bitmap;
+        result
+    }
+
+    /// Returns A ∪ B as a sorted array.
+    pub fn union_sorted(&self, other: &Self) -> Vec<i32> {
+        let mut result = Vec::with_capacity(self.len + other.len);
+        union_merge(&self.to_sorted_set(), &other.to_sorted_set(), &mut result);
+        result
+    }
+
+    /// Returns A \ B as a sorted array.
+    pub fn difference_sorted<I: SegmentIntersect>(&self, other: &Self) -> Vec<i32> {
+        self.difference::<I>(other).to_sorted_set()
+    }
+
+    // Builds a set with this set's hash size, where `fill` appends the sorted
+    // items of each segment in turn.
+    fn build_segments<F>(&self, bitmap: Vec<u8>, mut fill: F) -> Fesia<H, S, LANES>
+    where
+        F: FnMut(usize, &mut Vec<i32>),
+    {
+        let segment_count = self.segment_count();
+        let mut sizes = Vec::with_capacity(segment_count);
+        let mut offsets = Vec::with_capacity(segment_count);
+        let mut reordered_set = Vec::new();
+
+        for segment in 0..segment_count {
+            let offset = reordered_set.len();
+            fill(segment, &mut reordered_set);
+            offsets.push(offset as i32);
+            sizes.push((reordered_set.len() - offset) as i32);
+        }
+
+        Fesia {
+            bitmap,
+            sizes,
+            offsets,
+            len: reordered_set.len(),
+            reordered_set,
+            hash_size: self.hash_size,
+            hash_t: PhantomData,
+            segment_t: PhantomData,
+        }
+    }
+}
+
+impl<H, S, const LANES: usize> Fesia<H, S, LANES>
+where
+    H: IntegerHash,
+    S: SimdElement + MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+    Simd<S, LANES>: BitAnd<Output=Simd<S, LANES>> + SimdPartialEq<Mask=Mask<S, LANES>>,
+{
+    /// See `FesiaRef::union`.
+    pub fn union(&self, other: &Self) -> Self {
+        self.fesia_ref().union(&other.fesia_ref())
+    }
+
+    /// See `FesiaRef::difference`.
+    pub fn difference<I: SegmentIntersect>(&self, other: &Self) -> Self {
+        self.fesia_ref().difference::<I>(&other.fesia_ref())
+    }
+
+    pub fn union_sorted(&self, other: &Self) -> Vec<i32> {
+        self.fesia_ref().union_sorted(&other.fesia_ref())
+    }
+
+    pub fn difference_sorted<I: SegmentIntersect>(&self, other: &Self) -> Vec<i32> {
+        self.fesia_ref().difference_sorted::<I>(&other.fesia_ref())
+    }
+}
+
+fn union_merge(set_a: &[i32], set_b: &[i32], result: &mut Vec<i32>) {
+    let mut i_a = 0;
+    let mut i_b = 0;
+    while i_a < set_a.len() && i_b < set_b.len() {
+        let a = set_a[i_a];
+        let b = set_b[i_b];
+        result.push(a.min(b));
+        i_a += (a <= b) as usize;
+        i_b += (b <= a) as usize;
+    }
+    result.extend_from_slice(&set_a[i_a..]);
+    result.extend_from_slice(&set_b[i_b..]);
+}
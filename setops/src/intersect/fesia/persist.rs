@@ -0,0 +1,245 @@
+/// Binary format for FESIA sets, allowing them to be built offline and loaded
+/// zero-copy, e.g., from a memory-mapped file.
+///
+/// Header (native endianness)
+/// - 32-bit magic: "FESI"
+/// - u32 version
+/// - u32 flags: LSB is 1 if written in little endian, 0 otherwise.
+/// - u32 hash function id (`IntegerHash::ID`)
+/// - u32 segment width in bits (bits of `S`)
+/// - u32 `LANES`
+/// - u64 hash_size
+/// - u64 set length
+///
+/// Data
+/// - bitmap: hash_size / 8 bytes
+/// - sizes: one i32 per segment
+/// - offsets: one i32 per segment
+/// - reordered set: set length i32s, including the slack of each segment
+/// - padding: PADDING zeroed i32s, as segment kernels may read past the end of
+///   the last segment.
+
+use std::{
+    io::{self, Write},
+    marker::PhantomData,
+    mem::size_of,
+    simd::*,
+    simd::cmp::*,
+    ops::BitAnd,
+    fmt,
+};
+use super::{Fesia, FesiaRef, IntegerHash, segment_bits};
+
+const MAGIC: [u8; 4] = *b"FESI";
+const VERSION: u32 = 1;
+const LITTLE_ENDIAN_BIT: u32 = 1;
+const HEADER_LEN: usize = 40;
+const PADDING: usize = 64;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FesiaLoadError {
+    BadMagic,
+    BadVersion(u32),
+    BadEndianness,
+    BadHash { expected: u32, found: u32 },
+    BadSegmentBits { expected: u32, found: u32 },
+    BadLanes { expected: u32, found: u32 },
+    BadHashSize(u64),
+    Misaligned,
+    Truncated { expected: usize, found: usize },
+    /// Sizes or offsets which do not describe disjoint, in-order segments of
+    /// the reordered set, or a length which overflows.
+    Corrupt,
+}
+
+impl fmt::Display for FesiaLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FesiaLoadError::BadMagic => write!(f, "bad magic"),
+            FesiaLoadError::BadVersion(v) => write!(f, "unsupported version {}", v),
+            FesiaLoadError::BadEndianness => write!(f, "bad endianness"),
+            FesiaLoadError::BadHash { expected, found } =>
+                write!(f, "hash id {} does not match expected {}", found, expected),
+            FesiaLoadError::BadSegmentBits { expected, found } =>
+                write!(f, "segment width {} does not match expected {}", found, expected),
+            FesiaLoadError::BadLanes { expected, found } =>
+                write!(f, "lane count {} does not match expected {}", found, expected),
+            FesiaLoadError::BadHashSize(h) => write!(f, "invalid hash size {}", h),
+            FesiaLoadError::Misaligned => write!(f, "buffer is not aligned to i32"),
+            FesiaLoadError::Truncated { expected, found } =>
+                write!(f, "expected {} bytes, found {}", expected, found),
+            FesiaLoadError::Corrupt => write!(f, "corrupt segment table"),
+        }
+    }
+}
+
+impl std::error::Error for FesiaLoadError {}
+
+impl<'a, H, S, const LANES: usize> FesiaRef<'a, H, S, LANES>
+where
+    H: IntegerHash,
+    S: SimdElement + MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+    Simd<S, LANES>: BitAnd<Output=Simd<S, LANES>> + SimdPartialEq<Mask=Mask<S, LANES>>,
+{
+    pub fn to_writer(&self, mut writer: impl Write) -> io::Result<()> {
+        let flags = if cfg!(target_endian = "little") { LITTLE_ENDIAN_BIT } else { 0 };
+
+        writer.write_all(&MAGIC)?;
+        for word in [VERSION, flags, H::ID, segment_bits::<S>() as u32, LANES as u32] {
+            writer.write_all(&word.to_ne_bytes())?;
+        }
+        writer.write_all(&(self.hash_size as u64).to_ne_bytes())?;
+        writer.write_all(&(self.reordered_set.len() as u64).to_ne_bytes())?;
+
+        writer.write_all(self.bitmap)?;
+        writer.write_all(i32_bytes(self.sizes))?;
+        writer.write_all(i32_bytes(self.offsets))?;
+        writer.write_all(i32_bytes(self.reordered_set))?;
+        writer.write_all(i32_bytes(&[0; PADDING]))?;
+
+        Ok(())
+    }
+
+    /// Borrows a set written by `to_writer` without copying. `bytes` must be
+    /// aligned to i32, which is always the case for memory-mapped files.
+    /// Lengths and the segment table are validated, so corrupt input is
+    /// rejected rather than read out of bounds. Items are not checked against
+    /// their hashes, so a corrupt set may give wrong results but is memory
+    /// safe.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, FesiaLoadError> {
+        if !(bytes.as_ptr() as usize).is_multiple_of(std::mem::align_of::<i32>()) {
+            return Err(FesiaLoadError::Misaligned);
+        }
+        if bytes.len() < HEADER_LEN {
+            return Err(FesiaLoadError::Truncated { expected: HEADER_LEN, found: bytes.len() });
+        }
+        if bytes[0..4] != MAGIC[..] {
+            return Err(FesiaLoadError::BadMagic);
+        }
+
+        let word = |i: usize| u32::from_ne_bytes(bytes[4 + i*4..8 + i*4].try_into().unwrap());
+        let long = |at: usize| u64::from_ne_bytes(bytes[at..at + 8].try_into().unwrap());
+
+        let version = word(0);
+        if version != VERSION {
+            return Err(FesiaLoadError::BadVersion(version));
+        }
+        let little_endian = word(1) & LITTLE_ENDIAN_BIT != 0;
+        if little_endian != cfg!(target_endian = "little") {
+            return Err(FesiaLoadError::BadEndianness);
+        }
+        check(word(2), H::ID, |expected, found| FesiaLoadError::BadHash { expected, found })?;
+        check(word(3), segment_bits::<S>() as u32,
+            |expected, found| FesiaLoadError::BadSegmentBits { expected, found })?;
+        check(word(4), LANES as u32,
+            |expected, found| FesiaLoadError::BadLanes { expected, found })?;
+
+        let hash_size = long(24);
+        let set_len = usize::try_from(long(32)).map_err(|_| FesiaLoadError::Corrupt)?;
+        let segment_width = segment_bits::<S>() as u64;
+        // Masked hashes are non-negative i32s, which bounds the hash size.
+        if !hash_size.is_power_of_two() || !hash_size.is_multiple_of(segment_width * LANES as u64) ||
+            hash_size > 1 << 31
+        {
+            return Err(FesiaLoadError::BadHashSize(hash_size));
+        }
+        let hash_size = hash_size as usize;
+        let segment_count = hash_size / segment_width as usize;
+
+        let bitmap_len = hash_size / u8::BITS as usize;
+        let expected = (2 * segment_count).checked_add(set_len)
+            .and_then(|words| words.checked_add(PADDING))
+            .and_then(|words| words.checked_mul(size_of::<i32>()))
+            .and_then(|data| data.checked_add(HEADER_LEN + bitmap_len))
+            .ok_or(FesiaLoadError::Corrupt)?;
+        if bytes.len() < expected {
+            return Err(FesiaLoadError::Truncated { expected, found: bytes.len() });
+        }
+
+        let bitmap = &bytes[HEADER_LEN..HEADER_LEN + bitmap_len];
+        let rest = &bytes[HEADER_LEN + bitmap_len..];
+        let sizes = bytes_i32(rest, 0, segment_count);
+        let offsets = bytes_i32(rest, segment_count, segment_count);
+        let reordered_set = bytes_i32(rest, 2 * segment_count, set_len);
+        let len = check_segments(sizes, offsets, set_len)?;
+
+        Ok(Self {
+            bitmap,
+            sizes,
+            offsets,
+            reordered_set,
+            len,
+            hash_size,
+            hash_t: PhantomData,
+            segment_t: PhantomData,
+        })
+    }
+
+    pub fn to_fesia(&self) -> Fesia<H, S, LANES> {
+        Fesia {
+            bitmap: self.bitmap.to_vec(),
+            sizes: self.sizes.to_vec(),
+            offsets: self.offsets.to_vec(),
+            reordered_set: self.reordered_set.to_vec(),
+            len: self.len,
+            hash_size: self.hash_size,
+            hash_t: PhantomData,
+            segment_t: PhantomData,
+        }
+    }
+}
+
+impl<H, S, const LANES: usize> Fesia<H, S, LANES>
+where
+    H: IntegerHash,
+    S: SimdElement + MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+    Simd<S, LANES>: BitAnd<Output=Simd<S, LANES>> + SimdPartialEq<Mask=Mask<S, LANES>>,
+{
+    pub fn to_writer(&self, writer: impl Write) -> io::Result<()> {
+        self.fesia_ref().to_writer(writer)
+    }
+}
+
+fn check(
+    found: u32,
+    expected: u32,
+    err: fn(u32, u32) -> FesiaLoadError) -> Result<(), FesiaLoadError>
+{
+    if found == expected { Ok(()) } else { Err(err(expected, found)) }
+}
+
+// Kernels index segments unchecked and bound reads by the start of the next
+// segment, so segments must be in order, disjoint and within the reordered
+// set. Returns the number of items.
+fn check_segments(sizes: &[i32], offsets: &[i32], set_len: usize) -> Result<usize, FesiaLoadError> {
+    let mut end = 0;
+    let mut len = 0;
+    for (&size, &offset) in sizes.iter().zip(offsets) {
+        let (Ok(size), Ok(offset)) = (usize::try_from(size), usize::try_from(offset)) else {
+            return Err(FesiaLoadError::Corrupt);
+        };
+        match offset.checked_add(size) {
+            Some(segment_end) if offset >= end && segment_end <= set_len => end = segment_end,
+            _ => return Err(FesiaLoadError::Corrupt),
+        }
+        len += size;
+    }
+    Ok(len)
+}
+
+fn i32_bytes(data: &[i32]) -> &[u8] {
+    unsafe {
+        std::slice::from_raw_parts(data.as_ptr() as *const u8, std::mem::size_of_val(data))
+    }
+}
+
+// `bytes` must be aligned to i32 and hold at least `start + len` i32s.
+fn bytes_i32(bytes: &[u8], start: usize, len: usize) -> &[i32] {
+    debug_assert!((bytes.as_ptr() as usize).is_multiple_of(std::mem::align_of::<i32>()));
+    debug_assert!((start + len) * size_of::<i32>() <= bytes.len());
+    unsafe {
+        std::slice::from_raw_parts((bytes.as_ptr() as *const i32).add(start), len)
+    }
+}
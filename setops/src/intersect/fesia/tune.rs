@@ -0,0 +1,181 @@
+/// Build statistics, and selection of the hash function and scale from the
+/// input rather than a fixed `HashScale`.
+
+use std::{
+    simd::*,
+    simd::cmp::*,
+    ops::BitAnd,
+};
+use super::*;
+
+// Bound on the tuned hash size relative to the input length, as some hashes
+// can never spread skewed inputs, e.g., `IdentityHash` on multiples of 2^k.
+const MAX_TUNED_SCALE: usize = 64;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BuildStats {
+    pub hash_size: usize,
+    pub segment_count: usize,
+    /// `segment_histogram[i]` is the number of segments holding i items.
+    pub segment_histogram: Vec<usize>,
+    pub max_segment_size: usize,
+    pub avg_segment_size: f64,
+    /// Fraction of bitmap bits which are set.
+    pub bitmap_density: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashFamily {
+    Identity,
+    Mix,
+    MultiplyShift,
+    #[cfg(target_feature = "sse4.2")]
+    Crc32,
+    Tabulation,
+}
+
+/// Result of `tune_hash`. Build with `Fesia::<H, ..>::with_hash_size` using
+/// the hash type of `family`, or with `from_sorted_scaled` and `hash_scale`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HashTuning {
+    pub family: HashFamily,
+    pub hash_size: usize,
+    pub hash_scale: HashScale,
+}
+
+impl HashFamily {
+    /// All families in increasing order of hashing cost.
+    pub const ALL: &'static [HashFamily] = &[
+        HashFamily::Identity,
+        HashFamily::MultiplyShift,
+        #[cfg(target_feature = "sse4.2")]
+        HashFamily::Crc32,
+        HashFamily::Mix,
+        HashFamily::Tabulation,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HashFamily::Identity => "id",
+            HashFamily::Mix => "mix",
+            HashFamily::MultiplyShift => "mulshift",
+            #[cfg(target_feature = "sse4.2")]
+            HashFamily::Crc32 => "crc",
+            HashFamily::Tabulation => "tab",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|family| family.name() == name)
+    }
+
+    fn required_hash_size<S>(self, sorted: &[i32], max_segment: usize) -> usize {
+        match self {
+            HashFamily::Identity =>
+                required_hash_size::<IdentityHash, S>(sorted, max_segment),
+            HashFamily::Mix =>
+                required_hash_size::<MixHash, S>(sorted, max_segment),
+            HashFamily::MultiplyShift =>
+                required_hash_size::<MultiplyShiftHash, S>(sorted, max_segment),
+            #[cfg(target_feature = "sse4.2")]
+            HashFamily::Crc32 =>
+                required_hash_size::<Crc32Hash, S>(sorted, max_segment),
+            HashFamily::Tabulation =>
+                required_hash_size::<TabulationHash, S>(sorted, max_segment),
+        }
+    }
+}
+
+/// Picks the hash family giving the smallest bitmap for which no segment of
+/// width `S` holds more than `max_segment` items, e.g.,
+/// `SegmentIntersectAvx2::MAX_KERNEL`. Ties go to the cheaper hash.
+pub fn tune_hash<S>(sorted: &[i32], max_segment: usize) -> HashTuning {
+    let (family, hash_size) = HashFamily::ALL.iter()
+        .map(|&family| (family, family.required_hash_size::<S>(sorted, max_segment)))
+        .min_by_key(|&(_, hash_size)| hash_size)
+        .unwrap();
+
+    HashTuning {
+        family,
+        hash_size,
+        hash_scale: hash_size as f64 / sorted.len().max(1) as f64,
+    }
+}
+
+impl<H, S, const LANES: usize> Fesia<H, S, LANES>
+where
+    H: IntegerHash,
+    S: SimdElement + MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+    Simd<S, LANES>: BitAnd<Output=Simd<S, LANES>> + SimdPartialEq<Mask=Mask<S, LANES>>,
+{
+    /// Builds with the smallest hash size for which no segment holds more than
+    /// `max_segment` items, up to `MAX_TUNED_SCALE` times the input length.
+    pub fn with_max_segment(sorted: &[i32], max_segment: usize) -> Self {
+        Self::with_hash_size(sorted, required_hash_size::<H, S>(sorted, max_segment))
+    }
+
+    pub fn build_stats(&self) -> BuildStats {
+        self.fesia_ref().build_stats()
+    }
+}
+
+impl<'a, H, S, const LANES: usize> FesiaRef<'a, H, S, LANES>
+where
+    H: IntegerHash,
+    S: SimdElement + MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+    Simd<S, LANES>: BitAnd<Output=Simd<S, LANES>> + SimdPartialEq<Mask=Mask<S, LANES>>,
+{
+    pub fn build_stats(&self) -> BuildStats {
+        let max_segment_size = self.sizes.iter().copied().max().unwrap_or(0) as usize;
+        let mut segment_histogram = vec![0; max_segment_size + 1];
+        for &size in self.sizes {
+            segment_histogram[size as usize] += 1;
+        }
+
+        let ones: usize = self.bitmap.iter().map(|b| b.count_ones() as usize).sum();
+
+        BuildStats {
+            hash_size: self.hash_size,
+            segment_count: self.segment_count(),
+            segment_histogram,
+            max_segment_size,
+            avg_segment_size: self.len as f64 / self.segment_count() as f64,
+            bitmap_density: ones as f64 / self.hash_size as f64,
+        }
+    }
+}
+
+fn required_hash_size<H, S>(sorted: &[i32], max_segment: usize) -> usize
+where
+    H: IntegerHash,
+{
+    let segment_bits = segment_bits::<S>();
+    let max_segment = max_segment.max(1);
+
+    let mut hashes = vec![0; sorted.len()];
+    H::hash_batch(sorted, &mut hashes);
+
+    let lower_bound = sorted.len().div_ceil(max_segment) * segment_bits;
+    let upper_bound = (sorted.len() * MAX_TUNED_SCALE).next_power_of_two()
+        .clamp(MIN_HASH_SIZE, MAX_HASH_SIZE);
+
+    let mut hash_size = lower_bound.next_power_of_two().max(MIN_HASH_SIZE);
+    let mut counts = Vec::new();
+    while hash_size < upper_bound {
+        counts.clear();
+        counts.resize(hash_size / segment_bits, 0);
+
+        let fits = hashes.iter().all(|&hash| {
+            let count = &mut counts[mask_hash::<H>(hash, hash_size) as usize / segment_bits];
+            *count += 1;
+            *count <= max_segment
+        });
+        if fits {
+            break;
+        }
+        hash_size *= 2;
+    }
+    hash_size
+}
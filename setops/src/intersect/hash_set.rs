@@ -0,0 +1,200 @@
+#![cfg(feature = "simd")]
+/// An open-addressing hash set of 32-bit items whose table is split into
+/// groups of `LANES` slots. An item hashes to a group, which is compared
+/// against the item with a single SIMD comparison; probing only moves on to
+/// the next group while the current one has no free slot. Unlike
+/// `std::collections::HashSet`, the table is laid out for vectorized lookups,
+/// which `probe_array_group_hash_set` batches over a sorted array.
+
+use std::{
+    marker::PhantomData,
+    simd::*,
+    simd::cmp::*,
+};
+
+use crate::{
+    Set, Cardinality,
+    visitor::Visitor,
+    intersect::fesia::{IntegerHash, MixHash},
+};
+
+// Marks a free slot. An item with these bits is tracked by `has_empty`.
+const EMPTY: i32 = i32::MIN;
+// Items hashed together by `hash_probe`.
+const HASH_BATCH: usize = 16;
+
+pub type GroupHashSetSse<T>    = GroupHashSet<T, 4>;
+pub type GroupHashSetAvx2<T>   = GroupHashSet<T, 8>;
+pub type GroupHashSetAvx512<T> = GroupHashSet<T, 16>;
+
+/// Items a `GroupHashSet` can hold, stored by their bits as `i32`.
+pub trait GroupItem: Copy + Eq {
+    fn to_bits(self) -> i32;
+}
+
+impl GroupItem for i32 {
+    fn to_bits(self) -> i32 {
+        self
+    }
+}
+
+impl GroupItem for u32 {
+    fn to_bits(self) -> i32 {
+        self as i32
+    }
+}
+
+/// Items can only be inserted, so no tombstones are needed: an item is always
+/// found before the first group with a free slot along its probe sequence.
+pub struct GroupHashSet<T, const LANES: usize> {
+    slots: Vec<i32>,
+    len: usize,
+    has_empty: bool,
+    _item: PhantomData<T>,
+}
+
+impl<T, const LANES: usize> GroupHashSet<T, LANES>
+where
+    T: GroupItem,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Sizes the table so that `capacity` items keep it at most 7/8 full.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let groups = (capacity * 8 / 7 / LANES + 1).next_power_of_two();
+        Self {
+            slots: vec![EMPTY; groups * LANES],
+            len: 0,
+            has_empty: false,
+            _item: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns whether `item` was newly inserted.
+    pub fn insert(&mut self, item: T) -> bool {
+        let key = item.to_bits();
+        if key == EMPTY {
+            let inserted = !self.has_empty;
+            self.has_empty = true;
+            self.len += inserted as usize;
+            return inserted;
+        }
+        if (self.len + 1) * 8 > self.slots.len() * 7 {
+            self.grow();
+        }
+        match self.find(key, MixHash::hash(key)) {
+            Ok(()) => false,
+            Err(slot) => {
+                self.slots[slot] = key;
+                self.len += 1;
+                true
+            }
+        }
+    }
+
+    pub fn contains(&self, item: T) -> bool {
+        let key = item.to_bits();
+        self.contains_hashed(key, MixHash::hash(key))
+    }
+
+    /// Visits the items of `sorted` which are in the set, in order. Items are
+    /// hashed `HASH_BATCH` at a time with the vectorized `hash_batch`.
+    pub fn hash_probe<V>(&self, sorted: &[T], visitor: &mut V)
+    where
+        V: Visitor<T>,
+    {
+        let mut keys = [0; HASH_BATCH];
+        let mut hashes = [0; HASH_BATCH];
+        for chunk in sorted.chunks(HASH_BATCH) {
+            let keys = &mut keys[..chunk.len()];
+            let hashes = &mut hashes[..chunk.len()];
+            for (key, &item) in keys.iter_mut().zip(chunk) {
+                *key = item.to_bits();
+            }
+            MixHash::hash_batch(keys, hashes);
+
+            for ((&item, &key), &hash) in chunk.iter().zip(keys.iter()).zip(hashes.iter()) {
+                if self.contains_hashed(key, hash) {
+                    visitor.visit(item);
+                }
+            }
+        }
+    }
+
+    fn contains_hashed(&self, key: i32, hash: i32) -> bool {
+        if key == EMPTY {
+            return self.has_empty;
+        }
+        self.find(key, hash).is_ok()
+    }
+
+    /// Walks the groups from `hash`, returning `Err` with the first free slot
+    /// if `key` is not present. The table always has a free slot, so this
+    /// terminates.
+    fn find(&self, key: i32, hash: i32) -> Result<(), usize> {
+        let group_mask = self.slots.len() / LANES - 1;
+        let mut group = hash as u32 as usize & group_mask;
+        loop {
+            let slots = Simd::<i32, LANES>::from_slice(&self.slots[group * LANES..]);
+            if slots.simd_eq(Simd::splat(key)).any() {
+                return Ok(());
+            }
+            let free = slots.simd_eq(Simd::splat(EMPTY)).to_bitmask();
+            if free != 0 {
+                return Err(group * LANES + free.trailing_zeros() as usize);
+            }
+            group = (group + 1) & group_mask;
+        }
+    }
+
+    fn grow(&mut self) {
+        let grown = vec![EMPTY; self.slots.len() * 2];
+        let slots = std::mem::replace(&mut self.slots, grown);
+        for key in slots.into_iter().filter(|&key| key != EMPTY) {
+            if let Err(slot) = self.find(key, MixHash::hash(key)) {
+                self.slots[slot] = key;
+            }
+        }
+    }
+}
+
+impl<T, const LANES: usize> Default for GroupHashSet<T, LANES>
+where
+    T: GroupItem,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const LANES: usize> Set<T> for GroupHashSet<T, LANES>
+where
+    T: GroupItem,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    fn from_sorted(sorted: &[T]) -> Self {
+        let mut set = Self::with_capacity(sorted.len());
+        for &item in sorted {
+            set.insert(item);
+        }
+        set
+    }
+}
+
+impl<T, const LANES: usize> Cardinality for GroupHashSet<T, LANES> {
+    fn cardinality(&self) -> usize {
+        self.len
+    }
+}
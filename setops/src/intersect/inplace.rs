@@ -0,0 +1,110 @@
+#![cfg(feature = "simd")]
+/// Shared driver for the in-place block kernels, e.g., `shuffling_sse_inplace`.
+/// Matches are compacted into the front of the left set. A block of the left
+/// set is held in a register until it is finished with, and only then are its
+/// matches written, so the write cursor never overtakes an unread block.
+
+use std::simd::*;
+
+use crate::{visitor::SliceWriter, instructions::load_unsafe, util::slice_as_i32_mut};
+
+/// `block_mask` compares a block of `set_a` with the block of `set_b` at the
+/// given pointer and `emit` writes the matches of a block, as a
+/// `SimdVisitor` would. Blocks are compared as i32 but ordered as `T`, which
+/// must be sized and aligned as i32.
+#[inline]
+pub fn block_inplace<T, const W: usize>(
+    set_a: &mut [T],
+    set_b: &[T],
+    block_mask: impl Fn(Simd<i32, W>, *const i32) -> u64,
+    emit: impl Fn(&mut SliceWriter<i32>, Simd<i32, W>, u64)) -> usize
+where
+    T: Ord + Copy,
+    LaneCount<W>: SupportedLaneCount,
+{
+    assert!(std::mem::size_of::<T>() == std::mem::size_of::<i32>());
+    assert!(std::mem::align_of::<T>() >= std::mem::align_of::<i32>());
+    let ptr_b = set_b.as_ptr() as *const i32;
+
+    let st_a = (set_a.len() / W) * W;
+    let st_b = (set_b.len() / W) * W;
+
+    if st_a == 0 || st_b == 0 {
+        return merge_inplace(set_a, 0, &[], 0, set_b);
+    }
+
+    let mut count = 0;
+    let mut i_a: usize = 0;
+    let mut i_b: usize = 0;
+    let mut v_a: Simd<i32, W> = unsafe { load_unsafe(set_a.as_ptr() as *const i32) };
+    let mut mask = 0;
+    while i_a < st_a && i_b < st_b {
+        mask |= block_mask(v_a, unsafe { ptr_b.add(i_b) });
+
+        let a_max = unsafe { *set_a.get_unchecked(i_a + W - 1) };
+        let b_max = unsafe { *set_b.get_unchecked(i_b + W - 1) };
+
+        if a_max <= b_max {
+            let mut writer = SliceWriter::from(slice_as_i32_mut(&mut set_a[count..i_a + W]));
+            emit(&mut writer, v_a, mask);
+            count += writer.position();
+            mask = 0;
+
+            i_a += W;
+            if i_a < st_a {
+                v_a = unsafe { load_unsafe(set_a.as_ptr().add(i_a) as *const i32) };
+            }
+        }
+        i_b += W * (b_max <= a_max) as usize;
+    }
+
+    // The current block may still match the rest of `set_b`. Its matches so
+    // far are all smaller than any such match, so can be written first.
+    let mut pending = [set_a[0]; W];
+    let mut pending_len = 0;
+    if i_a < st_a {
+        pending.copy_from_slice(&set_a[i_a..i_a + W]);
+        pending_len = W;
+
+        let mut writer = SliceWriter::from(slice_as_i32_mut(&mut set_a[count..i_a + W]));
+        emit(&mut writer, v_a, mask);
+        count += writer.position();
+        i_a += W;
+    }
+    merge_inplace(set_a, count, &pending[..pending_len], i_a, &set_b[i_b..])
+}
+
+/// Merges `pending` followed by `set_a[from..]` with `set_b`, writing matches
+/// to `set_a` from `count`. Requires `count <= from`.
+#[inline]
+pub fn merge_inplace<T>(
+    set_a: &mut [T],
+    mut count: usize,
+    pending: &[T],
+    from: usize,
+    set_b: &[T]) -> usize
+where
+    T: Ord + Copy,
+{
+    debug_assert!(count <= from);
+    let len_a = pending.len() + set_a.len() - from;
+
+    let mut idx_a = 0;
+    let mut idx_b = 0;
+    while idx_a < len_a && idx_b < set_b.len() {
+        let value_a = if idx_a < pending.len() {
+            pending[idx_a]
+        } else {
+            set_a[from + idx_a - pending.len()]
+        };
+        let value_b = set_b[idx_b];
+
+        if value_a == value_b {
+            set_a[count] = value_a;
+            count += 1;
+        }
+        idx_a += (value_a <= value_b) as usize;
+        idx_b += (value_b <= value_a) as usize;
+    }
+    count
+}
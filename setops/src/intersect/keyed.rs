@@ -0,0 +1,147 @@
+/// Intersection of sorted records (array-of-structs) by a projected key, so
+/// records need neither be `Ord` nor 4 bytes wide. Records must be sorted by
+/// strictly increasing key. Matches are visited as `(left, right)` record
+/// pairs; wrap the visitor in `Map` to keep a single side.
+
+use crate::visitor::{Visitor, VecWriter, Clearable};
+use super::Intersect2;
+
+/// Classical merge comparing projected keys.
+pub fn merge_by_key<R, K, F, V>(set_a: &[R], set_b: &[R], key: F, visitor: &mut V)
+where
+    R: Copy,
+    K: Ord,
+    F: Fn(&R) -> K,
+    V: Visitor<(R, R)>,
+{
+    let mut idx_a = 0;
+    let mut idx_b = 0;
+
+    while idx_a < set_a.len() && idx_b < set_b.len() {
+        let key_a = key(&set_a[idx_a]);
+        let key_b = key(&set_b[idx_b]);
+
+        if key_a == key_b {
+            visitor.visit((set_a[idx_a], set_b[idx_b]));
+            idx_a += 1;
+            idx_b += 1;
+        } else {
+            idx_a += (key_a < key_b) as usize;
+            idx_b += (key_b < key_a) as usize;
+        }
+    }
+}
+
+/// Galloping search of each record in `small` within `large`. Pairs are
+/// visited as `(small, large)`.
+pub fn galloping_by_key<R, K, F, V>(small: &[R], mut large: &[R], key: F, visitor: &mut V)
+where
+    R: Copy,
+    K: Ord,
+    F: Fn(&R) -> K,
+    V: Visitor<(R, R)>,
+{
+    for record in small {
+        if large.is_empty() {
+            break;
+        }
+        let target = key(record);
+
+        let mut hi = 1;
+        while hi < large.len() && key(&large[hi - 1]) < target {
+            hi *= 2;
+        }
+        let lo = hi / 2;
+        let hi = hi.min(large.len());
+        let position = lo + large[lo..hi].partition_point(|item| key(item) < target);
+
+        large = &large[position..];
+        if let Some(found) = large.first() {
+            if key(found) == target {
+                visitor.visit((*record, *found));
+                large = &large[1..];
+            }
+        }
+    }
+}
+
+/// Extracts the keys of both inputs into contiguous arrays (structure-of-
+/// arrays), intersects them with any array kernel, including SIMD kernels, and
+/// maps matched keys back to their records. Extraction and mapping back each
+/// take a pass over both inputs, so this only pays off when the kernel is much
+/// faster than `merge_by_key`; for skewed inputs prefer `galloping_by_key`.
+/// Allocates the key arrays, see `intersect_by_key_with` to reuse them.
+pub fn intersect_by_key<R, K, F, V>(
+    set_a: &[R],
+    set_b: &[R],
+    key: F,
+    intersect: Intersect2<[K], VecWriter<K>>,
+    visitor: &mut V)
+where
+    R: Copy,
+    K: Ord + Copy,
+    F: Fn(&R) -> K,
+    V: Visitor<(R, R)>,
+{
+    intersect_by_key_with(set_a, set_b, key, intersect, &mut KeyBuffers::new(), visitor)
+}
+
+/// Key arrays for `intersect_by_key_with`. They grow to the largest inputs
+/// seen and are then reused.
+pub struct KeyBuffers<K> {
+    keys_a: Vec<K>,
+    keys_b: Vec<K>,
+    matched: VecWriter<K>,
+}
+
+impl<K> KeyBuffers<K> {
+    pub fn new() -> Self {
+        Self {
+            keys_a: Vec::new(),
+            keys_b: Vec::new(),
+            matched: VecWriter::new(),
+        }
+    }
+}
+
+impl<K> Default for KeyBuffers<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// As `intersect_by_key`, extracting keys into `buffers` rather than fresh
+/// arrays.
+pub fn intersect_by_key_with<R, K, F, V>(
+    set_a: &[R],
+    set_b: &[R],
+    key: F,
+    intersect: Intersect2<[K], VecWriter<K>>,
+    buffers: &mut KeyBuffers<K>,
+    visitor: &mut V)
+where
+    R: Copy,
+    K: Ord + Copy,
+    F: Fn(&R) -> K,
+    V: Visitor<(R, R)>,
+{
+    let KeyBuffers { keys_a, keys_b, matched } = buffers;
+    keys_a.clear();
+    keys_a.extend(set_a.iter().map(&key));
+    keys_b.clear();
+    keys_b.extend(set_b.iter().map(&key));
+    matched.clear();
+    intersect(keys_a, keys_b, matched);
+
+    let mut idx_a = 0;
+    let mut idx_b = 0;
+    for &matched in matched.as_ref() {
+        while keys_a[idx_a] < matched {
+            idx_a += 1;
+        }
+        while keys_b[idx_b] < matched {
+            idx_b += 1;
+        }
+        visitor.visit((set_a[idx_a], set_b[idx_b]));
+    }
+}
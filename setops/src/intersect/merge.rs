@@ -1,6 +1,8 @@
 use std::cmp::Ordering;
 
-use crate::{visitor::{Visitor, BsrVisitor}, bsr::BsrRef};
+use smallvec::SmallVec;
+
+use crate::{visitor::{Visitor, BsrVisitor}, bsr::{BsrRef, BsrState}};
 
 /// Classical set intersection via merge. Original author unknown.
 // Inspired by https://highlyscalable.wordpress.com/2012/06/05/fast-intersection-sorted-lists-sse/
@@ -59,9 +61,10 @@ where
     }
 }
 
-pub fn branchless_merge_bsr<'a, V>(set_a: BsrRef<'a>, set_b: BsrRef<'a>, visitor: &mut V)
+pub fn branchless_merge_bsr<'a, S, V>(set_a: BsrRef<'a, S>, set_b: BsrRef<'a, S>, visitor: &mut V)
 where
-    V: BsrVisitor,
+    S: BsrState,
+    V: BsrVisitor<S>,
 {
     let mut idx_a = 0;
     let mut idx_b = 0;
@@ -74,7 +77,7 @@ where
 
         if base_a == base_b {
             let new_state = state_a & state_b;
-            if new_state != 0 {
+            if new_state != S::zero() {
                 visitor.visit_bsr(base_a, new_state);
             }
             idx_a += 1;
@@ -86,6 +89,43 @@ where
     }
 }
 
+/// K-way merge of BSR sets, scanning the others for each base of the smallest
+/// set as in `fesia::merge_k` and ANDing the states of matching bases.
+pub fn merge_k_bsr<'a, V>(sets: &[BsrRef<'a>], visitor: &mut V)
+where
+    V: BsrVisitor,
+{
+    let mut set_spans: SmallVec<[BsrRef<'a>; 8]> = sets.iter().copied().collect();
+    set_spans.sort_unstable_by_key(|s| s.len());
+    let smallest = set_spans[0];
+
+    'target_loop:
+    for (&target, &state) in smallest {
+        let mut state = state;
+
+        'set_loop:
+        for set in &mut set_spans[1..] {
+            for (i, &base) in set.bases.iter().enumerate() {
+                if target < base {
+                    // `target` not found
+                    *set = set.advanced_by(i);
+                    continue 'target_loop;
+                }
+                else if base == target {
+                    state &= set.states[i];
+                    *set = set.advanced_by(i + 1);
+                    if state == 0 {
+                        continue 'target_loop;
+                    }
+                    continue 'set_loop;
+                }
+            }
+            return;
+        }
+        visitor.visit_bsr(target, state);
+    }
+}
+
 pub const fn const_intersect<const LEN: usize>(
     set_a: &[i32],
     set_b: &[i32]) -> [i32; LEN]
@@ -0,0 +1,102 @@
+/// Intersections where the operands have different representations: a small
+/// query kept as a sorted array against a large set pre-built in BSR, FESIA
+/// or hash set form. Results are visited in the order of the array, so they
+/// are sorted.
+
+use std::{collections::HashSet, hash::Hash};
+
+use crate::{
+    visitor::Visitor,
+    bsr::{BsrRef, BsrState},
+};
+use super::galloping::binary_search;
+#[cfg(feature = "simd")]
+use {
+    std::{simd::*, simd::cmp::*, ops::BitAnd},
+    crate::intersect::{
+        fesia::{FesiaRef, IntegerHash},
+        hash_set::{GroupHashSet, GroupItem},
+    },
+};
+
+/// Gallops over the bases of `large` for the base of each run of array items,
+/// then tests each item's bit in the matching state.
+pub fn galloping_array_bsr<S, V>(small: &[u32], mut large: BsrRef<'_, S>, visitor: &mut V)
+where
+    S: BsrState,
+    V: Visitor<u32>,
+{
+    let mut i = 0;
+    while i < small.len() && !large.is_empty() {
+        let base = small[i] >> S::SHIFT;
+
+        let mut offset = 1;
+        while offset < large.len() && large.bases[offset] <= base {
+            offset *= 2;
+        }
+
+        let lo: isize = (offset / 2) as isize;
+        let hi: isize = (large.len() as isize - 1).min(offset as isize);
+
+        let large_idx = binary_search(large.bases, base, lo, hi);
+        large = large.advanced_by(large_idx);
+
+        let state = match large.bases.first() {
+            Some(&b) if b == base => large.states[0],
+            _ => S::zero(),
+        };
+        while i < small.len() && small[i] >> S::SHIFT == base {
+            if state & S::bit(small[i]) != S::zero() {
+                visitor.visit(small[i]);
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Hashes each array item into `large`'s hash table, without building a
+/// FESIA set for the array. See `FesiaRef::hash_probe`.
+#[cfg(feature = "simd")]
+pub fn probe_array_fesia<H, S, V, const LANES: usize>(
+    small: &[i32],
+    large: FesiaRef<'_, H, S, LANES>,
+    visitor: &mut V)
+where
+    H: IntegerHash,
+    S: SimdElement + MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+    Simd<S, LANES>: BitAnd<Output=Simd<S, LANES>> + SimdPartialEq<Mask=Mask<S, LANES>>,
+    V: Visitor<i32>,
+{
+    large.hash_probe(small, visitor)
+}
+
+/// Looks up each array item in `large` in turn. `HashSet` does not expose its
+/// table, so lookups are not batched or vectorized;
+/// `probe_array_group_hash_set` is the SIMD alternative.
+pub fn probe_array_hash_set<T, V>(small: &[T], large: &HashSet<T>, visitor: &mut V)
+where
+    T: Copy + Eq + Hash,
+    V: Visitor<T>,
+{
+    for item in small {
+        if large.contains(item) {
+            visitor.visit(*item);
+        }
+    }
+}
+
+/// Hashes array items in batches and compares each against a whole group of
+/// `large`'s slots at once. See `GroupHashSet::hash_probe`.
+#[cfg(feature = "simd")]
+pub fn probe_array_group_hash_set<T, V, const LANES: usize>(
+    small: &[T],
+    large: &GroupHashSet<T, LANES>,
+    visitor: &mut V)
+where
+    T: GroupItem,
+    LaneCount<LANES>: SupportedLaneCount,
+    V: Visitor<T>,
+{
+    large.hash_probe(small, visitor)
+}
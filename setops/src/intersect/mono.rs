@@ -138,3 +138,139 @@ pub fn qfilter_c_mono(set_a: &[i32], set_b: &[i32], set_c: &mut [i32]) -> usize
     qfilter_c(set_a, set_b, set_c)
 }
     
+pub fn naive_merge_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    naive_merge(set_a, set_b, visitor);
+}
+    
+pub fn branchless_merge_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    branchless_merge(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "ssse3"))]
+pub fn shuffling_sse_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    shuffling_sse(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "ssse3"))]
+pub fn shuffling_sse_branch_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    shuffling_sse_branch(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "avx2"))]
+pub fn shuffling_avx2_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    shuffling_avx2(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "avx2"))]
+pub fn shuffling_avx2_branch_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    shuffling_avx2_branch(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "avx512f"))]
+pub fn shuffling_avx512_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    shuffling_avx512(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "avx512f"))]
+pub fn shuffling_avx512_branch_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    shuffling_avx512_branch(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "ssse3"))]
+pub fn broadcast_sse_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    broadcast_sse(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "ssse3"))]
+pub fn broadcast_sse_branch_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    broadcast_sse_branch(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "avx2"))]
+pub fn broadcast_avx2_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    broadcast_avx2(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "avx2"))]
+pub fn broadcast_avx2_branch_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    broadcast_avx2_branch(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "avx512f"))]
+pub fn broadcast_avx512_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    broadcast_avx512(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "avx512f"))]
+pub fn broadcast_avx512_branch_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    broadcast_avx512_branch(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "ssse3"))]
+pub fn bmiss_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    bmiss(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "ssse3"))]
+pub fn bmiss_branch_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    bmiss_branch(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "ssse3"))]
+pub fn bmiss_sttni_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    bmiss_sttni(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "ssse3"))]
+pub fn bmiss_sttni_branch_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    bmiss_sttni_branch(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "ssse3"))]
+pub fn qfilter_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    qfilter(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "ssse3"))]
+pub fn qfilter_branch_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    qfilter_branch(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "avx512f"))]
+pub fn vp2intersect_emulation_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    vp2intersect_emulation(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "avx512f"))]
+pub fn vp2intersect_emulation_branch_mono_u32(set_a: &[u32], set_b: &[u32], visitor: &mut VecWriter<u32>)
+{
+    vp2intersect_emulation_branch(set_a, set_b, visitor);
+}
+    
+#[cfg(all(feature = "simd", target_feature = "ssse3"))]
+pub fn qfilter_c_mono_u32(set_a: &[u32], set_b: &[u32], set_c: &mut [u32]) -> usize
+{
+    qfilter_c(set_a, set_b, set_c)
+}
+    
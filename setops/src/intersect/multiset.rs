@@ -0,0 +1,357 @@
+/// Multiset operations over non-decreasing inputs, i.e., sorted sequences which
+/// may contain duplicates. An item occurring `m` times in one input and `n`
+/// times in the other is visited `min(m, n)` times by an intersection and
+/// `max(m, n)` times by a bag union. `naive_merge` and `branchless_merge`
+/// already intersect multisets this way as each match consumes one item from
+/// either side.
+///
+/// The `multiset_broadcast` kernels compare whole blocks of both inputs. The
+/// `_sse`, `_avx2` and `_avx512` galloping variants only use SIMD to measure
+/// the length of each run. The search and the comparison of runs are the same
+/// scalar code as the plain versions, so they only help when runs are longer
+/// than a vector.
+
+use std::cmp::Ordering;
+#[cfg(feature = "simd")]
+use std::simd::*;
+#[cfg(feature = "simd")]
+use std::simd::cmp::*;
+
+use crate::visitor::Visitor;
+#[cfg(feature = "simd")]
+use crate::instructions::load_unsafe;
+
+/// Bag union via merge. Equal items consume one item from each input, so runs
+/// are visited as many times as in the longer of the two.
+pub fn bag_union_merge<T, V>(set_a: &[T], set_b: &[T], visitor: &mut V)
+where
+    T: Ord + Copy,
+    V: Visitor<T>,
+{
+    let mut idx_a = 0;
+    let mut idx_b = 0;
+
+    while idx_a < set_a.len() && idx_b < set_b.len() {
+        let value_a = set_a[idx_a];
+        let value_b = set_b[idx_b];
+
+        match value_a.cmp(&value_b) {
+            Ordering::Less => {
+                visitor.visit(value_a);
+                idx_a += 1;
+            },
+            Ordering::Greater => {
+                visitor.visit(value_b);
+                idx_b += 1;
+            },
+            Ordering::Equal => {
+                visitor.visit(value_a);
+                idx_a += 1;
+                idx_b += 1;
+            },
+        }
+    }
+
+    visit_all(&set_a[idx_a..], visitor);
+    visit_all(&set_b[idx_b..], visitor);
+}
+
+/// Multiset intersection via galloping. Each run in the smaller input is
+/// located in the larger input by exponential search, after which the lengths
+/// of both runs are compared.
+pub fn multiset_galloping<T, V>(small: &[T], large: &[T], visitor: &mut V)
+where
+    T: Ord + Copy,
+    V: Visitor<T>,
+{
+    multiset_galloping_impl(small, large, visitor, run_length)
+}
+
+/// Bag union via galloping. Items of the larger input which are skipped over
+/// while searching are visited as-is.
+pub fn bag_union_galloping<T, V>(small: &[T], large: &[T], visitor: &mut V)
+where
+    T: Ord + Copy,
+    V: Visitor<T>,
+{
+    bag_union_galloping_impl(small, large, visitor, run_length)
+}
+
+/// 4 lane version which measures runs with 128-bit vectors. Only run lengths
+/// use SIMD, see the module documentation.
+#[cfg(feature = "simd")]
+pub fn multiset_galloping_sse<T, V>(small: &[T], large: &[T], visitor: &mut V)
+where
+    T: SimdElement + Ord,
+    Simd<T, 4>: SimdPartialEq<Mask=Mask<T::Mask, 4>>,
+    V: Visitor<T>,
+{
+    multiset_galloping_impl(small, large, visitor, simd_run_length::<T, 4>)
+}
+
+/// 8 lane version which measures runs with 256-bit vectors. Only run lengths
+/// use SIMD.
+#[cfg(feature = "simd")]
+pub fn multiset_galloping_avx2<T, V>(small: &[T], large: &[T], visitor: &mut V)
+where
+    T: SimdElement + Ord,
+    Simd<T, 8>: SimdPartialEq<Mask=Mask<T::Mask, 8>>,
+    V: Visitor<T>,
+{
+    multiset_galloping_impl(small, large, visitor, simd_run_length::<T, 8>)
+}
+
+/// 16 lane version which measures runs with 512-bit vectors. Only run
+/// lengths use SIMD.
+#[cfg(feature = "simd")]
+pub fn multiset_galloping_avx512<T, V>(small: &[T], large: &[T], visitor: &mut V)
+where
+    T: SimdElement + Ord,
+    Simd<T, 16>: SimdPartialEq<Mask=Mask<T::Mask, 16>>,
+    V: Visitor<T>,
+{
+    multiset_galloping_impl(small, large, visitor, simd_run_length::<T, 16>)
+}
+
+/// Bag union variants which measure runs with 128, 256 and 512-bit vectors.
+/// As with `multiset_galloping_sse`, only run lengths use SIMD.
+#[cfg(feature = "simd")]
+pub fn bag_union_galloping_sse<T, V>(small: &[T], large: &[T], visitor: &mut V)
+where
+    T: SimdElement + Ord,
+    Simd<T, 4>: SimdPartialEq<Mask=Mask<T::Mask, 4>>,
+    V: Visitor<T>,
+{
+    bag_union_galloping_impl(small, large, visitor, simd_run_length::<T, 4>)
+}
+
+#[cfg(feature = "simd")]
+pub fn bag_union_galloping_avx2<T, V>(small: &[T], large: &[T], visitor: &mut V)
+where
+    T: SimdElement + Ord,
+    Simd<T, 8>: SimdPartialEq<Mask=Mask<T::Mask, 8>>,
+    V: Visitor<T>,
+{
+    bag_union_galloping_impl(small, large, visitor, simd_run_length::<T, 8>)
+}
+
+#[cfg(feature = "simd")]
+pub fn bag_union_galloping_avx512<T, V>(small: &[T], large: &[T], visitor: &mut V)
+where
+    T: SimdElement + Ord,
+    Simd<T, 16>: SimdPartialEq<Mask=Mask<T::Mask, 16>>,
+    V: Visitor<T>,
+{
+    bag_union_galloping_impl(small, large, visitor, simd_run_length::<T, 16>)
+}
+
+/// Multiset intersection comparing a block of 4 items from each input, like
+/// `broadcast_sse`, while counting multiplicities. See `multiset_broadcast`.
+#[cfg(feature = "simd")]
+pub fn multiset_broadcast_sse<T, V>(set_a: &[T], set_b: &[T], visitor: &mut V)
+where
+    T: SimdElement<Mask = i32> + Ord,
+    Simd<T, 4>: SimdPartialOrd<Mask = Mask<i32, 4>>,
+    V: Visitor<T>,
+{
+    multiset_broadcast::<T, V, 4>(set_a, set_b, visitor)
+}
+
+#[cfg(feature = "simd")]
+pub fn multiset_broadcast_avx2<T, V>(set_a: &[T], set_b: &[T], visitor: &mut V)
+where
+    T: SimdElement<Mask = i32> + Ord,
+    Simd<T, 8>: SimdPartialOrd<Mask = Mask<i32, 8>>,
+    V: Visitor<T>,
+{
+    multiset_broadcast::<T, V, 8>(set_a, set_b, visitor)
+}
+
+#[cfg(feature = "simd")]
+pub fn multiset_broadcast_avx512<T, V>(set_a: &[T], set_b: &[T], visitor: &mut V)
+where
+    T: SimdElement<Mask = i32> + Ord,
+    Simd<T, 16>: SimdPartialOrd<Mask = Mask<i32, 16>>,
+    V: Visitor<T>,
+{
+    multiset_broadcast::<T, V, 16>(set_a, set_b, visitor)
+}
+
+/// Both blocks always start at the beginning of a run, so every item below
+/// `bound`, the smaller of the two block maxima, has its whole run inside both
+/// blocks. Such an item in block `a` is visited if its rank within its run is
+/// less than the number of equal items in block `b`, which visits each item
+/// `min(m, n)` times. Items below `bound` are then consumed from both blocks.
+/// If neither block has any, one of them is a single run of `bound`, which is
+/// counted on both sides with `simd_run_length`.
+#[cfg(feature = "simd")]
+#[inline]
+fn multiset_broadcast<T, V, const LANES: usize>(set_a: &[T], set_b: &[T], visitor: &mut V)
+where
+    T: SimdElement<Mask = i32> + Ord,
+    LaneCount<LANES>: SupportedLaneCount,
+    Simd<T, LANES>: SimdPartialOrd<Mask = Mask<i32, LANES>>,
+    V: Visitor<T>,
+{
+    let lanes = Simd::<i32, LANES>::from_array(std::array::from_fn(|i| i as i32));
+
+    let mut i_a = 0;
+    let mut i_b = 0;
+    while i_a + LANES <= set_a.len() && i_b + LANES <= set_b.len() {
+        let v_a: Simd<T, LANES> = unsafe { load_unsafe(set_a.as_ptr().add(i_a)) };
+        let v_b: Simd<T, LANES> = unsafe { load_unsafe(set_b.as_ptr().add(i_b)) };
+        let bound = set_a[i_a + LANES - 1].min(set_b[i_b + LANES - 1]);
+
+        let mut rank_a = Simd::<i32, LANES>::splat(0);
+        let mut count_b = Simd::<i32, LANES>::splat(0);
+        for k in 0..LANES {
+            let earlier = lanes.simd_gt(Simd::splat(k as i32));
+            rank_a -= (v_a.simd_eq(Simd::splat(set_a[i_a + k])) & earlier).to_int();
+            count_b -= v_a.simd_eq(Simd::splat(set_b[i_b + k])).to_int();
+        }
+
+        let below_a = v_a.simd_lt(Simd::splat(bound));
+        let below_b = v_b.simd_lt(Simd::splat(bound));
+        let mut matches = (below_a & rank_a.simd_lt(count_b)).to_bitmask();
+        while matches != 0 {
+            visitor.visit(set_a[i_a + matches.trailing_zeros() as usize]);
+            matches &= matches - 1;
+        }
+
+        let consumed_a = below_a.to_bitmask().count_ones() as usize;
+        let consumed_b = below_b.to_bitmask().count_ones() as usize;
+        if consumed_a + consumed_b == 0 {
+            let count_a = simd_run_length::<T, LANES>(&set_a[i_a..], bound);
+            let count_b = simd_run_length::<T, LANES>(&set_b[i_b..], bound);
+            for _ in 0..count_a.min(count_b) {
+                visitor.visit(bound);
+            }
+            i_a += count_a;
+            i_b += count_b;
+        }
+        else {
+            i_a += consumed_a;
+            i_b += consumed_b;
+        }
+    }
+    super::branchless_merge(&set_a[i_a..], &set_b[i_b..], visitor);
+}
+
+#[inline]
+fn multiset_galloping_impl<T, V, R>(
+    small: &[T],
+    large: &[T],
+    visitor: &mut V,
+    run_length: R)
+where
+    T: Ord + Copy,
+    V: Visitor<T>,
+    R: Fn(&[T], T) -> usize,
+{
+    let (mut small, mut large) =
+        if small.len() > large.len() { (large, small) } else { (small, large) };
+
+    while !small.is_empty() {
+        let target = small[0];
+        let count_small = run_length(small, target);
+
+        large = &large[lower_bound(large, target)..];
+        let count_large = run_length(large, target);
+
+        for _ in 0..count_small.min(count_large) {
+            visitor.visit(target);
+        }
+
+        small = &small[count_small..];
+        large = &large[count_large..];
+    }
+}
+
+#[inline]
+fn bag_union_galloping_impl<T, V, R>(
+    small: &[T],
+    large: &[T],
+    visitor: &mut V,
+    run_length: R)
+where
+    T: Ord + Copy,
+    V: Visitor<T>,
+    R: Fn(&[T], T) -> usize,
+{
+    let (mut small, mut large) =
+        if small.len() > large.len() { (large, small) } else { (small, large) };
+
+    while !small.is_empty() {
+        let target = small[0];
+        let count_small = run_length(small, target);
+
+        let lower = lower_bound(large, target);
+        visit_all(&large[..lower], visitor);
+        large = &large[lower..];
+        let count_large = run_length(large, target);
+
+        for _ in 0..count_small.max(count_large) {
+            visitor.visit(target);
+        }
+
+        small = &small[count_small..];
+        large = &large[count_large..];
+    }
+
+    visit_all(large, visitor);
+}
+
+/// Index of the first item not less than `target`, found by doubling the
+/// search bound then binary searching within it.
+#[inline]
+fn lower_bound<T: Ord + Copy>(large: &[T], target: T) -> usize {
+    if large.first().is_none_or(|&first| first >= target) {
+        return 0;
+    }
+
+    let mut hi = 1;
+    while hi < large.len() && large[hi] < target {
+        hi *= 2;
+    }
+    // large[hi / 2] < target by the loop invariant.
+    let lo = hi / 2 + 1;
+    let hi = hi.min(large.len());
+
+    lo + large[lo..hi].partition_point(|&item| item < target)
+}
+
+#[inline]
+fn run_length<T: Ord + Copy>(items: &[T], value: T) -> usize {
+    items.iter().take_while(|&&item| item == value).count()
+}
+
+#[cfg(feature = "simd")]
+#[inline]
+fn simd_run_length<T, const LANES: usize>(items: &[T], value: T) -> usize
+where
+    T: SimdElement + Ord,
+    LaneCount<LANES>: SupportedLaneCount,
+    Simd<T, LANES>: SimdPartialEq<Mask=Mask<T::Mask, LANES>>,
+{
+    let target = Simd::<T, LANES>::splat(value);
+
+    let mut length = 0;
+    while length + LANES <= items.len() {
+        let block: Simd<T, LANES> = unsafe {
+            load_unsafe(items.as_ptr().add(length))
+        };
+        let run = target.simd_eq(block).to_bitmask().trailing_ones() as usize;
+        length += run;
+        if run < LANES {
+            return length;
+        }
+    }
+    length + run_length(&items[length..], value)
+}
+
+#[inline]
+fn visit_all<T: Copy, V: Visitor<T>>(items: &[T], visitor: &mut V) {
+    for &item in items {
+        visitor.visit(item);
+    }
+}
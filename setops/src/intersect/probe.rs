@@ -0,0 +1,152 @@
+/// Prepared probes preprocess the query side of a one-vs-many intersection
+/// once, so it can then be intersected against a batch of targets. This is
+/// the common pattern in graph workloads where one neighbour list is
+/// intersected against the neighbour lists of each of its neighbours.
+
+use crate::{
+    bsr::{BsrVec, BsrRef, Intersect2BsrV},
+    Set,
+};
+#[cfg(feature = "simd")]
+use {
+    std::{simd::*, simd::cmp::*, ops::BitAnd},
+    crate::{
+        visitor::Visitor,
+        intersect::{self, fesia::{Fesia, IntegerHash}},
+        instructions::load_unsafe,
+    },
+};
+
+pub trait Probe<Target, V> {
+    fn probe(&self, target: Target, visitor: &mut V);
+
+    /// Intersects the prepared set with each target in turn, creating a
+    /// visitor for the i-th target with `factory(i)`.
+    fn probe_batch<I, F>(&self, targets: I, mut factory: F) -> Vec<V>
+    where
+        I: IntoIterator<Item=Target>,
+        F: FnMut(usize) -> V,
+    {
+        targets.into_iter()
+            .enumerate()
+            .map(|(i, target)| {
+                let mut visitor = factory(i);
+                self.probe(target, &mut visitor);
+                visitor
+            })
+            .collect()
+    }
+}
+
+/// Query converted to BSR once, intersected against BSR targets with any BSR
+/// algorithm.
+pub struct BsrProbe<V> {
+    bsr: BsrVec,
+    intersect: Intersect2BsrV<V>,
+}
+
+impl<V> BsrProbe<V> {
+    pub fn new(sorted: &[u32], intersect: Intersect2BsrV<V>) -> Self {
+        Self::from_bsr(BsrVec::from_sorted(sorted), intersect)
+    }
+
+    pub fn from_bsr(bsr: BsrVec, intersect: Intersect2BsrV<V>) -> Self {
+        Self { bsr, intersect }
+    }
+
+    pub fn bsr(&self) -> &BsrVec {
+        &self.bsr
+    }
+}
+
+impl<'t, V> Probe<BsrRef<'t>, V> for BsrProbe<V> {
+    fn probe(&self, target: BsrRef<'t>, visitor: &mut V) {
+        (self.intersect)(self.bsr.bsr_ref(), target, visitor);
+    }
+}
+
+/// Query items splatted into broadcast vectors once, each compared against a
+/// whole block of `LANES` target items. A target block is compared with every
+/// query item up to its last item, so each query item costs one comparison
+/// and the target is read a vector at a time. Output is in sorted order.
+#[cfg(feature = "simd")]
+pub struct BroadcastProbe<T, const LANES: usize>
+where
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    set: Vec<T>,
+    broadcasts: Vec<Simd<i32, LANES>>,
+}
+
+#[cfg(feature = "simd")]
+pub type BroadcastProbeSse<T>    = BroadcastProbe<T, 4>;
+#[cfg(feature = "simd")]
+pub type BroadcastProbeAvx2<T>   = BroadcastProbe<T, 8>;
+#[cfg(feature = "simd")]
+pub type BroadcastProbeAvx512<T> = BroadcastProbe<T, 16>;
+
+#[cfg(feature = "simd")]
+impl<T, const LANES: usize> BroadcastProbe<T, LANES>
+where
+    T: Ord + Copy,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    pub fn new(sorted: &[T]) -> Self {
+        assert!(std::mem::size_of::<T>() == std::mem::size_of::<i32>());
+        let broadcasts = sorted.iter()
+            .map(|item| Simd::splat(unsafe { *(item as *const T as *const i32) }))
+            .collect();
+        Self { set: sorted.to_vec(), broadcasts }
+    }
+
+    pub fn set(&self) -> &[T] {
+        &self.set
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<'t, T, V, const LANES: usize> Probe<&'t [T], V> for BroadcastProbe<T, LANES>
+where
+    T: Ord + Copy,
+    V: Visitor<T>,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    fn probe(&self, target: &'t [T], visitor: &mut V) {
+        let ptr = target.as_ptr() as *const i32;
+        let st_target = (target.len() / LANES) * LANES;
+
+        let mut i_set = 0;
+        let mut i_target = 0;
+        while i_set < self.set.len() && i_target < st_target {
+            let block: Simd<i32, LANES> = unsafe { load_unsafe(ptr.add(i_target)) };
+            let block_max = target[i_target + LANES - 1];
+
+            // Items up to the block's maximum are above all earlier blocks, so
+            // can only match within this block.
+            while i_set < self.set.len() && self.set[i_set] <= block_max {
+                if block.simd_eq(self.broadcasts[i_set]).any() {
+                    visitor.visit(self.set[i_set]);
+                }
+                i_set += 1;
+            }
+            i_target += LANES;
+        }
+        intersect::branchless_merge(&self.set[i_set..], &target[i_target..], visitor);
+    }
+}
+
+/// A FESIA query probes each target item into its hash table, so targets can
+/// remain as sorted arrays. Output is in sorted order.
+#[cfg(feature = "simd")]
+impl<'t, H, S, V, const LANES: usize> Probe<&'t [i32], V> for Fesia<H, S, LANES>
+where
+    H: IntegerHash,
+    S: SimdElement + MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+    Simd<S, LANES>: BitAnd<Output=Simd<S, LANES>> + SimdPartialEq<Mask=Mask<S, LANES>>,
+    V: Visitor<i32>,
+{
+    fn probe(&self, target: &'t [i32], visitor: &mut V) {
+        self.hash_probe(target, visitor);
+    }
+}
@@ -1,22 +1,51 @@
 #![allow(dead_code)]
 include!(concat!(env!("OUT_DIR"), "/qfilter_c.rs"));
 use libc::c_int;
+use super::RESULT_SLACK;
 
+/// Items the C kernel can intersect, selecting the entry point which orders
+/// them correctly: signed for `i32` and unsigned for `u32`.
+pub trait QFilterItem: Ord + Copy {
+    /// # Safety
+    /// Neither set may be empty, and `result` must hold the smaller set plus
+    /// `RESULT_SLACK` items, as for `qfilter_c`.
+    unsafe fn intersect_c(set_a: &[Self], set_b: &[Self], result: &mut [Self]) -> usize;
+}
+
+impl QFilterItem for i32 {
+    unsafe fn intersect_c(set_a: &[i32], set_b: &[i32], result: &mut [i32]) -> usize {
+        unsafe {
+            intersect_qfilter_uint_b4_v2(
+                set_a.as_ptr(), set_a.len() as c_int,
+                set_b.as_ptr(), set_b.len() as c_int,
+                result.as_mut_ptr()) as usize
+        }
+    }
+}
+
+impl QFilterItem for u32 {
+    unsafe fn intersect_c(set_a: &[u32], set_b: &[u32], result: &mut [u32]) -> usize {
+        unsafe {
+            intersect_qfilter_u32_b4_v2(
+                set_a.as_ptr(), set_a.len() as c_int,
+                set_b.as_ptr(), set_b.len() as c_int,
+                result.as_mut_ptr()) as usize
+        }
+    }
+}
+
+/// The C kernel loads its first vectors with aligned loads before checking
+/// sizes, so empty sets, whose pointers are dangling, must not reach it.
+/// It also stores whole vectors, writing up to `RESULT_SLACK` items past
+/// its count, so `result` must hold the smaller set plus `RESULT_SLACK` items.
 #[cfg(target_feature = "ssse3")]
 pub fn qfilter_c<T>(set_a: &[T], set_b: &[T], result: &mut [T]) -> usize
 where
-    T: Ord + Copy,
+    T: QFilterItem,
 {
-    assert!(std::mem::size_of::<T>() == std::mem::size_of::<c_int>());
-    assert!(result.len() >= set_a.len().min(set_b.len()));
-    let ptr_a = set_a.as_ptr() as *const c_int;
-    let ptr_b = set_b.as_ptr() as *const c_int;
-    let ptr_result = result.as_ptr() as *mut c_int;
-
-    unsafe {
-        intersect_qfilter_uint_b4_v2(
-            ptr_a, set_a.len() as c_int,
-            ptr_b, set_b.len() as c_int,
-            ptr_result) as usize
+    assert!(result.len() >= set_a.len().min(set_b.len()) + RESULT_SLACK);
+    if set_a.is_empty() || set_b.is_empty() {
+        return 0;
     }
+    unsafe { T::intersect_c(set_a, set_b, result) }
 }
@@ -7,8 +7,8 @@ use std::{
 };
 
 use crate::{
-    visitor::{Visitor, SimdVisitor4,SimdBsrVisitor4},
-    intersect, instructions::load_unsafe,
+    visitor::{Visitor, SimdVisitor4, SimdBsrVisitor4, BsrVisitor, visit_bsr_lanes},
+    intersect::{self, inplace}, instructions::load_unsafe,
     bsr::BsrRef,
     util::*,
 };
@@ -168,6 +168,44 @@ where
 }
 
 
+// In-place versions //
+
+/// As `shuffling_sse`, compacting matches into the front of `set_a` rather than
+/// visiting them. Returns the number of matches.
+#[cfg(target_feature = "ssse3")]
+pub fn shuffling_sse_inplace<T: Ord + Copy>(set_a: &mut [T], set_b: &[T]) -> usize {
+    inplace::block_inplace::<T, 4>(set_a, set_b, shuffling_mask,
+        |writer, v_a, mask| writer.visit_vector4(v_a, mask))
+}
+
+#[cfg(target_feature = "avx2")]
+pub fn shuffling_avx2_inplace<T: Ord + Copy>(set_a: &mut [T], set_b: &[T]) -> usize {
+    inplace::block_inplace::<T, 8>(set_a, set_b, shuffling_mask,
+        |writer, v_a, mask| writer.visit_vector8(v_a, mask))
+}
+
+#[cfg(target_feature = "avx512f")]
+pub fn shuffling_avx512_inplace<T: Ord + Copy>(set_a: &mut [T], set_b: &[T]) -> usize {
+    inplace::block_inplace::<T, 16>(set_a, set_b, shuffling_mask,
+        |writer, v_a, mask| writer.visit_vector16(v_a, mask))
+}
+
+// Compares a block of A with each rotation of a block of B.
+#[inline]
+fn shuffling_mask<const W: usize>(v_a: Simd<i32, W>, ptr_b: *const i32) -> u64
+where
+    LaneCount<W>: SupportedLaneCount,
+{
+    let mut v_b: Simd<i32, W> = unsafe { load_unsafe(ptr_b) };
+    let mut mask = Mask::splat(false);
+    for _ in 0..W {
+        mask |= v_a.simd_eq(v_b);
+        v_b = v_b.rotate_elements_left::<1>();
+    }
+    mask.to_bitmask()
+}
+
+
 // BSR implementations //
 
 #[cfg(target_feature = "ssse3")]
@@ -355,6 +393,67 @@ where
         visitor)
 }
 
+#[cfg(target_feature = "avx2")]
+pub fn shuffling_avx2_bsr64<'a, V>(set_a: BsrRef<'a, u64>, set_b: BsrRef<'a, u64>, visitor: &mut V)
+where
+    V: BsrVisitor<u64>,
+{
+    shuffling_bsr64::<V, 4>(set_a, set_b, visitor)
+}
+
+#[cfg(target_feature = "avx512f")]
+pub fn shuffling_avx512_bsr64<'a, V>(set_a: BsrRef<'a, u64>, set_b: BsrRef<'a, u64>, visitor: &mut V)
+where
+    V: BsrVisitor<u64>,
+{
+    shuffling_bsr64::<V, 8>(set_a, set_b, visitor)
+}
+
+// 64-bit states halve the lanes per register, so W bases are compared as u32
+// but their states are ANDed as W u64 lanes.
+#[inline]
+fn shuffling_bsr64<'a, V, const W: usize>(
+    set_a: BsrRef<'a, u64>,
+    set_b: BsrRef<'a, u64>,
+    visitor: &mut V)
+where
+    V: BsrVisitor<u64>,
+    LaneCount<W>: SupportedLaneCount,
+{
+    let st_a = (set_a.len() / W) * W;
+    let st_b = (set_b.len() / W) * W;
+
+    let mut i_a: usize = 0;
+    let mut i_b: usize = 0;
+    while i_a < st_a && i_b < st_b {
+        let base_a: Simd<u32, W> = unsafe{ load_unsafe(set_a.bases.as_ptr().add(i_a)) };
+        let mut base_b: Simd<u32, W> = unsafe{ load_unsafe(set_b.bases.as_ptr().add(i_b)) };
+        let state_a: Simd<u64, W> = unsafe{ load_unsafe(set_a.states.as_ptr().add(i_a)) };
+        let mut state_b: Simd<u64, W> = unsafe{ load_unsafe(set_b.states.as_ptr().add(i_b)) };
+
+        let mut state_all = Simd::<u64, W>::splat(0);
+        for _ in 0..W {
+            let base_mask = base_a.simd_eq(base_b).cast::<i64>();
+            state_all |= base_mask.select(state_a & state_b, Simd::splat(0));
+            base_b = base_b.rotate_elements_left::<1>();
+            state_b = state_b.rotate_elements_left::<1>();
+        }
+        let total_mask = state_all.simd_ne(Simd::splat(0)).to_bitmask();
+
+        visit_bsr_lanes(visitor, base_a, state_all, total_mask);
+
+        let a_max = unsafe { *set_a.bases.get_unchecked(i_a + W - 1) };
+        let b_max = unsafe { *set_b.bases.get_unchecked(i_b + W - 1) };
+
+        i_a += W * (a_max <= b_max) as usize;
+        i_b += W * (b_max <= a_max) as usize;
+    }
+    intersect::branchless_merge_bsr(
+        unsafe { set_a.advanced_by_unchecked(i_a) },
+        unsafe { set_b.advanced_by_unchecked(i_b) },
+        visitor)
+}
+
 
 
 
@@ -11,15 +11,20 @@
 use std::simd::*;
 use std::simd::cmp::*;
 
-use crate::{visitor::{Visitor, BsrVisitor}, intersect, instructions::load_unsafe, bsr::BsrRef};
+use crate::{
+    visitor::{Visitor, BsrVisitor},
+    intersect::{self, inplace},
+    instructions::load_unsafe,
+    bsr::BsrRef,
+};
 
 const NUM_LANES_IN_BOUND: usize = 32;
 
 /// 4 lane version used to intersect with 128-bit vectors, e.g., i32x4.
 pub fn galloping_sse<T, V>(small: &[T], large: &[T], visitor: &mut V)
 where
-    T: SimdElement + MaskElement + Ord + Default,
-    Simd<T, 4>: SimdPartialEq<Mask=Mask<T, 4>>,
+    T: SimdElement + Ord + Default,
+    Simd<T, 4>: SimdPartialEq<Mask=Mask<T::Mask, 4>>,
     V: Visitor<T>,
 {
     simd_galloping_impl::<T, V, 4>(small, large, visitor)
@@ -28,8 +33,8 @@ where
 /// 8 lane version used to intersect with 256-bit vectors, e.g., i32x8.
 pub fn galloping_avx2<T, V>(small: &[T], large: &[T], visitor: &mut V)
 where
-    T: SimdElement + MaskElement + Ord + Default,
-    Simd<T, 8>: SimdPartialEq<Mask=Mask<T, 8>>,
+    T: SimdElement + Ord + Default,
+    Simd<T, 8>: SimdPartialEq<Mask=Mask<T::Mask, 8>>,
     V: Visitor<T>,
 {
     simd_galloping_impl::<T, V, 8>(small, large, visitor)
@@ -39,8 +44,8 @@ where
 /// Only faster if native 512-bit vectors are supported.
 pub fn galloping_avx512<T, V>(small: &[T], large: &[T], visitor: &mut V)
 where
-    T: SimdElement + MaskElement + Ord + Default,
-    Simd<T, 16>: SimdPartialEq<Mask=Mask<T, 16>>,
+    T: SimdElement + Ord + Default,
+    Simd<T, 16>: SimdPartialEq<Mask=Mask<T::Mask, 16>>,
     V: Visitor<T>,
 {
     simd_galloping_impl::<T, V, 16>(small, large, visitor)
@@ -51,9 +56,9 @@ fn simd_galloping_impl<'a, T, V, const LANES: usize>(
     mut large: &'a[T],
     visitor: &mut V)
 where
-    T: SimdElement + MaskElement + Ord + Default,
+    T: SimdElement + Ord + Default,
     LaneCount<LANES>: SupportedLaneCount,
-    Simd<T, LANES>: SimdPartialEq<Mask=Mask<T, LANES>>,
+    Simd<T, LANES>: SimdPartialEq<Mask=Mask<T::Mask, LANES>>,
     V: Visitor<T>,
 {
     if small.len() > large.len() {
@@ -103,6 +108,71 @@ where
     intersect::branchless_merge(small, large, visitor)
 }
 
+/// As `galloping_sse`, compacting matches into the front of `small` rather
+/// than visiting them. Returns the number of matches. Unlike `galloping_sse`,
+/// `small` and `large` are never swapped.
+pub fn galloping_sse_inplace<T>(small: &mut [T], large: &[T]) -> usize
+where
+    T: SimdElement + Ord,
+    Simd<T, 4>: SimdPartialEq<Mask=Mask<T::Mask, 4>>,
+{
+    simd_galloping_inplace_impl::<T, 4>(small, large)
+}
+
+pub fn galloping_avx2_inplace<T>(small: &mut [T], large: &[T]) -> usize
+where
+    T: SimdElement + Ord,
+    Simd<T, 8>: SimdPartialEq<Mask=Mask<T::Mask, 8>>,
+{
+    simd_galloping_inplace_impl::<T, 8>(small, large)
+}
+
+pub fn galloping_avx512_inplace<T>(small: &mut [T], large: &[T]) -> usize
+where
+    T: SimdElement + Ord,
+    Simd<T, 16>: SimdPartialEq<Mask=Mask<T::Mask, 16>>,
+{
+    simd_galloping_inplace_impl::<T, 16>(small, large)
+}
+
+// Items of `small` are read strictly before their slot can be written, as
+// each is visited at most once.
+fn simd_galloping_inplace_impl<T, const LANES: usize>(small: &mut [T], mut large: &[T]) -> usize
+where
+    T: SimdElement + Ord,
+    LaneCount<LANES>: SupportedLaneCount,
+    Simd<T, LANES>: SimdPartialEq<Mask=Mask<T::Mask, LANES>>,
+{
+    let bound = LANES * NUM_LANES_IN_BOUND;
+
+    let mut count = 0;
+    let mut i = 0;
+    while i < small.len() && large.len() >= bound {
+        let target = small[i];
+
+        let target_block = gallop_wide(target, large, bound);
+
+        // Every remaining item is past the last full block of large.
+        if large[(target_block + 1) * bound - 1] < target {
+            large = &large[(target_block + 1) * bound..];
+            break;
+        }
+
+        large = &large[target_block * bound..];
+
+        let inner_offset: usize = reduce_search_bound(target, large, bound);
+
+        if block_compare::<T, LANES>(target, inner_offset, large).any() {
+            small[count] = target;
+            count += 1;
+        }
+        i += 1;
+    }
+
+    debug_assert!(i == small.len() || large.len() < bound);
+    inplace::merge_inplace(small, count, &[], i, large)
+}
+
 pub fn galloping_sse_bsr<'a, V>(
     small: BsrRef<'a>,
     large: BsrRef<'a>,
@@ -267,11 +337,11 @@ where
 fn block_compare<T, const LANES: usize>(
     target: T,
     inner_offset: usize,
-    large: &[T]) -> Mask<T, LANES>
+    large: &[T]) -> Mask<T::Mask, LANES>
 where
-    T: SimdElement + MaskElement + PartialOrd,
+    T: SimdElement + PartialOrd,
     LaneCount<LANES>: SupportedLaneCount,
-    Simd<T, LANES>: SimdPartialEq<Mask=Mask<T, LANES>>,
+    Simd<T, LANES>: SimdPartialEq<Mask=Mask<T::Mask, LANES>>,
 {
     let target_vec = Simd::<T, LANES>::splat(target);
     let qs = [
@@ -1,4 +1,4 @@
-use crate::{visitor::Visitor, Set};
+use crate::{visitor::Visitor, Set, Intersect, Cardinality, IterSorted};
 use std::{
     collections::{BTreeSet, HashSet},
     hash,
@@ -29,6 +29,33 @@ where
     }
 }
 
+impl<T> Intersect for HashSet<T>
+where
+    T: Copy + Eq + hash::Hash,
+{
+    fn intersect(&self, other: &Self) -> Self {
+        self.intersection(other).copied().collect()
+    }
+
+    fn intersect_count(&self, other: &Self) -> usize {
+        self.intersection(other).count()
+    }
+}
+
+impl<T> Cardinality for HashSet<T> {
+    fn cardinality(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: Copy + Ord> IterSorted<T> for HashSet<T> {
+    fn iter_sorted(&self) -> impl Iterator<Item = T> + '_ {
+        let mut items: Vec<T> = self.iter().copied().collect();
+        items.sort_unstable();
+        items.into_iter()
+    }
+}
+
 impl<T> Set<T> for BTreeSet<T>
 where
     T: Ord + Copy,
@@ -51,3 +78,25 @@ pub fn btree_set_intersect<T: Ord + Copy>(
         visitor.visit(item);
     }
 }
+
+impl<T: Ord + Copy> Intersect for BTreeSet<T> {
+    fn intersect(&self, other: &Self) -> Self {
+        self.intersection(other).copied().collect()
+    }
+
+    fn intersect_count(&self, other: &Self) -> usize {
+        self.intersection(other).count()
+    }
+}
+
+impl<T> Cardinality for BTreeSet<T> {
+    fn cardinality(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: Copy> IterSorted<T> for BTreeSet<T> {
+    fn iter_sorted(&self) -> impl Iterator<Item = T> + '_ {
+        self.iter().copied()
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
-    intersect, 
-    visitor::{Visitor, VecWriter, SliceWriter, Clearable},
+    intersect::{self, Intersect2InPlace},
+    visitor::{Visitor, BsrVisitor, VecWriter, SliceWriter, Clearable},
+    bsr::{BsrRef, BsrState},
 };
 
 
@@ -44,8 +45,9 @@ where
 
 
 /// Extends a 2-set intersection algorithm to k-set.
-/// Since SIMD algorithms cannot operate in place, to extend them to k sets, we
-/// must use two output vectors.
+/// Since visitors cannot write to the sets being read, to extend them to k
+/// sets, we must use two output vectors. See `svs_generic_inplace` for kernels
+/// which operate in place.
 /// Returns (intersection length, final output index)
 pub fn svs_generic<'a, T, S, V>(
     sets: &[S],
@@ -72,6 +74,55 @@ where
     left
 }
 
+/// As `svs_generic` for in-place kernels, e.g., `shuffling_sse_inplace`, which
+/// need a single output buffer. `out` must be able to hold the first set.
+/// Returns the intersection length.
+pub fn svs_generic_inplace<T, S>(
+    sets: &[S],
+    out: &mut [T],
+    intersect: Intersect2InPlace<T>) -> usize
+where
+    T: Copy,
+    S: AsRef<[T]>,
+{
+    assert!(sets.len() >= 2);
+
+    let first = sets[0].as_ref();
+    out[..first.len()].copy_from_slice(first);
+
+    let mut count = first.len();
+    for set in sets.iter().skip(1) {
+        count = intersect(&mut out[..count], set.as_ref());
+    }
+    count
+}
+
+/// As `svs_generic` for BSR sets, alternating between two BSR outputs.
+pub fn svs_bsr<'a, S, V>(
+    sets: &[BsrRef<'_, S>],
+    mut left: &'a mut V,
+    mut right: &'a mut V,
+    intersect: for<'b> fn(BsrRef<'b, S>, BsrRef<'b, S>, &mut V)
+) -> &'a mut V
+where
+    S: BsrState,
+    V: BsrVisitor<S> + Clearable,
+    for<'b> &'b V: Into<BsrRef<'b, S>>,
+{
+    assert!(sets.len() >= 2);
+
+    intersect(sets[0], sets[1], left);
+
+    for &set in sets.iter().skip(2) {
+        // Alternate output sets.
+        std::mem::swap(&mut left, &mut right);
+        left.clear();
+        intersect((&*right).into(), set, left);
+    }
+
+    left
+}
+
 pub fn svs_generic_c<'a, T, S>(
     sets: &[S],
     mut left: &'a mut [T],
@@ -84,17 +135,16 @@ where
 {
     assert!(sets.len() >= 2);
 
-    let new_len = intersect(sets[0].as_ref(), sets[1].as_ref(), left);
-    left = &mut left[..new_len];
+    let mut len = intersect(sets[0].as_ref(), sets[1].as_ref(), left);
 
     for set in sets.iter().skip(2) {
-        // Alternate output sets.
+        // Alternate output sets. Only the input is trimmed, so outputs keep
+        // their full size.
         std::mem::swap(&mut left, &mut right);
-        intersect(right.as_ref(), set.as_ref(), left);
-        left = &mut left[..new_len];
+        len = intersect(&right[..len], set.as_ref(), left);
     }
 
-    left
+    &mut left[..len]
 }
 
 /// Convenience function which makes calling svs_generic simpler for users and
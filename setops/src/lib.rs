@@ -5,11 +5,86 @@ pub mod intersect;
 pub mod visitor;
 pub mod instructions;
 pub mod bsr;
+pub mod graph;
+pub mod estimate;
+pub mod safe;
 mod util;
 
+use visitor::Counter;
+
+/// A set representation which can be built from sorted items. Together with
+/// `Intersect`, `Cardinality`, `IterSorted` and `ToSorted` this lets generic
+/// code work with any representation.
 pub trait Set<T>
 where
     T: Clone
 {
     fn from_sorted(sorted: &[T]) -> Self;
+
+    /// Converts from any other representation.
+    fn from_set(other: &impl ToSorted<T>) -> Self
+    where
+        Self: Sized,
+    {
+        Self::from_sorted(&other.to_sorted())
+    }
+}
+
+/// Intersection with a set of the same representation, using its default
+/// algorithm.
+pub trait Intersect {
+    fn intersect(&self, other: &Self) -> Self;
+
+    /// Size of the intersection, without building the result.
+    fn intersect_count(&self, other: &Self) -> usize;
+}
+
+pub trait Cardinality {
+    fn cardinality(&self) -> usize;
+}
+
+pub trait IterSorted<T> {
+    /// Iterates over items in increasing order.
+    fn iter_sorted(&self) -> impl Iterator<Item = T> + '_;
+}
+
+pub trait ToSorted<T> {
+    fn to_sorted(&self) -> Vec<T>;
+}
+
+impl<T, S: IterSorted<T>> ToSorted<T> for S {
+    fn to_sorted(&self) -> Vec<T> {
+        self.iter_sorted().collect()
+    }
+}
+
+/// A sorted array.
+impl<T: Clone> Set<T> for Vec<T> {
+    fn from_sorted(sorted: &[T]) -> Self {
+        sorted.to_vec()
+    }
+}
+
+impl<T: Ord + Copy> Intersect for Vec<T> {
+    fn intersect(&self, other: &Self) -> Self {
+        intersect::run_2set(self, other, intersect::branchless_merge)
+    }
+
+    fn intersect_count(&self, other: &Self) -> usize {
+        let mut counter = Counter::new();
+        intersect::branchless_merge(self, other, &mut counter);
+        counter.count()
+    }
+}
+
+impl<T> Cardinality for Vec<T> {
+    fn cardinality(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: Copy> IterSorted<T> for Vec<T> {
+    fn iter_sorted(&self) -> impl Iterator<Item = T> + '_ {
+        self.iter().copied()
+    }
 }
@@ -0,0 +1,350 @@
+/// Checked entry points over the kernels in `intersect` and `bsr`. Kernels
+/// assume sorted, duplicate-free inputs and some visitors assume spare
+/// capacity. Here inputs are validated once, when a `SortedSet` is built or a
+/// BSR set is passed in, and results are only written to visitors owned by
+/// this module, so no kernel can write out of bounds whichever it is. FESIA
+/// sets are validated when built or, for `FesiaRef`, by `from_bytes`.
+
+use std::fmt;
+
+use crate::{
+    intersect::{self, Intersect2, Intersect2C, IntersectK},
+    visitor::{VecWriter, Counter},
+    bsr::{BsrRef, BsrVec, BsrState, Intersect2Bsr},
+    Set, Intersect, Cardinality, IterSorted,
+};
+#[cfg(feature = "simd")]
+use crate::intersect::fesia::{FesiaIntersect, SegmentIntersect, SetWithHashScale, HashScale};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntersectError {
+    /// `items[index]` is smaller than the item before it.
+    Unsorted { index: usize },
+    /// `items[index]` equals the item before it.
+    Duplicate { index: usize },
+    TooFewSets { expected: usize, found: usize },
+    OutputTooSmall { required: usize, found: usize },
+    /// A BSR set whose bases and states differ in length.
+    LengthMismatch { bases: usize, states: usize },
+    /// A BSR set with an empty state at `index`.
+    EmptyState { index: usize },
+    InvalidHashScale,
+}
+
+impl fmt::Display for IntersectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntersectError::Unsorted { index } =>
+                write!(f, "item at {} is smaller than its predecessor", index),
+            IntersectError::Duplicate { index } =>
+                write!(f, "item at {} duplicates its predecessor", index),
+            IntersectError::TooFewSets { expected, found } =>
+                write!(f, "expected at least {} sets, found {}", expected, found),
+            IntersectError::OutputTooSmall { required, found } =>
+                write!(f, "output needs {} items, found {}", required, found),
+            IntersectError::LengthMismatch { bases, states } =>
+                write!(f, "{} bases but {} states", bases, states),
+            IntersectError::EmptyState { index } =>
+                write!(f, "state at {} is empty", index),
+            IntersectError::InvalidHashScale => write!(f, "hash scale must be positive"),
+        }
+    }
+}
+
+impl std::error::Error for IntersectError {}
+
+/// A strictly increasing sequence of items.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SortedSet<T>(Vec<T>);
+
+impl<T: Ord + Copy> SortedSet<T> {
+    /// Takes `items` as is, failing unless they are strictly increasing.
+    pub fn new(items: Vec<T>) -> Result<Self, IntersectError> {
+        check_sorted(&items)?;
+        Ok(Self(items))
+    }
+
+    pub fn from_unsorted(mut items: Vec<T>) -> Self {
+        items.sort_unstable();
+        items.dedup();
+        Self(items)
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<T: Ord + Copy> TryFrom<Vec<T>> for SortedSet<T> {
+    type Error = IntersectError;
+
+    fn try_from(items: Vec<T>) -> Result<Self, Self::Error> {
+        Self::new(items)
+    }
+}
+
+impl<T> AsRef<[T]> for SortedSet<T> {
+    fn as_ref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> From<SortedSet<T>> for Vec<T> {
+    fn from(set: SortedSet<T>) -> Self {
+        set.0
+    }
+}
+
+impl From<&SortedSet<u32>> for BsrVec {
+    fn from(set: &SortedSet<u32>) -> Self {
+        BsrVec::from_sorted(set.as_slice())
+    }
+}
+
+impl<T: Ord + Copy> Set<T> for SortedSet<T> {
+    /// Panics if `sorted` is not strictly increasing.
+    fn from_sorted(sorted: &[T]) -> Self {
+        Self::new(sorted.to_vec()).unwrap()
+    }
+}
+
+impl<T: Ord + Copy> Intersect for SortedSet<T> {
+    fn intersect(&self, other: &Self) -> Self {
+        Self(self.0.intersect(&other.0))
+    }
+
+    fn intersect_count(&self, other: &Self) -> usize {
+        self.0.intersect_count(&other.0)
+    }
+}
+
+impl<T> Cardinality for SortedSet<T> {
+    fn cardinality(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<T: Copy> IterSorted<T> for SortedSet<T> {
+    fn iter_sorted(&self) -> impl Iterator<Item = T> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+fn check_sorted<T: Ord>(items: &[T]) -> Result<(), IntersectError> {
+    for (i, pair) in items.windows(2).enumerate() {
+        if pair[1] < pair[0] {
+            return Err(IntersectError::Unsorted { index: i + 1 });
+        }
+        if pair[1] == pair[0] {
+            return Err(IntersectError::Duplicate { index: i + 1 });
+        }
+    }
+    Ok(())
+}
+
+/// Checks that a BSR set is well formed: one state per base, bases strictly
+/// increasing and no empty states.
+pub fn check_bsr<S: BsrState>(set: BsrRef<'_, S>) -> Result<(), IntersectError> {
+    if set.bases.len() != set.states.len() {
+        return Err(IntersectError::LengthMismatch {
+            bases: set.bases.len(),
+            states: set.states.len(),
+        });
+    }
+    check_sorted(set.bases)?;
+    match set.states.iter().position(|&s| s == S::zero()) {
+        Some(index) => Err(IntersectError::EmptyState { index }),
+        None => Ok(()),
+    }
+}
+
+fn check_count(found: usize) -> Result<(), IntersectError> {
+    if found < 2 {
+        return Err(IntersectError::TooFewSets { expected: 2, found });
+    }
+    Ok(())
+}
+
+// Most k-set kernels assume sets are ordered from smallest to largest.
+fn by_size<T>(sets: &[SortedSet<T>]) -> Vec<&[T]> {
+    let mut slices: Vec<&[T]> = sets.iter().map(|s| s.0.as_slice()).collect();
+    slices.sort_by_key(|s| s.len());
+    slices
+}
+
+pub fn intersect<T>(
+    set_a: &SortedSet<T>,
+    set_b: &SortedSet<T>,
+    intersect: Intersect2<[T], VecWriter<T>>) -> Vec<T>
+{
+    intersect::run_2set(set_a.as_ref(), set_b.as_ref(), intersect)
+}
+
+pub fn count<T>(
+    set_a: &SortedSet<T>,
+    set_b: &SortedSet<T>,
+    intersect: Intersect2<[T], Counter>) -> usize
+{
+    let mut counter = Counter::new();
+    intersect(set_a.as_ref(), set_b.as_ref(), &mut counter);
+    counter.count()
+}
+
+/// Runs a kernel writing directly to `out`, which must be able to hold the
+/// smaller set plus `intersect::RESULT_SLACK` items. Returns the number of
+/// items written.
+pub fn intersect_c_into<T>(
+    set_a: &SortedSet<T>,
+    set_b: &SortedSet<T>,
+    intersect: Intersect2C<[T]>,
+    out: &mut [T]) -> Result<usize, IntersectError>
+{
+    let required = set_a.0.len().min(set_b.0.len()) + intersect::RESULT_SLACK;
+    if out.len() < required {
+        return Err(IntersectError::OutputTooSmall { required, found: out.len() });
+    }
+    Ok(intersect(set_a.as_ref(), set_b.as_ref(), out))
+}
+
+pub fn intersect_c<T>(
+    set_a: &SortedSet<T>,
+    set_b: &SortedSet<T>,
+    intersect: Intersect2C<[T]>) -> Vec<T>
+where
+    T: Default + Copy,
+{
+    intersect::run_2set_c(set_a.as_ref(), set_b.as_ref(), intersect)
+}
+
+pub fn intersect_k<'a, T>(
+    sets: &'a [SortedSet<T>],
+    intersect: IntersectK<&'a [T], VecWriter<T>>) -> Result<Vec<T>, IntersectError>
+where
+    T: Ord + Copy,
+{
+    check_count(sets.len())?;
+    Ok(intersect::run_kset(&by_size(sets), intersect))
+}
+
+/// K-set intersection by repeated 2-set intersection with `intersect`.
+pub fn svs<T>(
+    sets: &[SortedSet<T>],
+    intersect: Intersect2<[T], VecWriter<T>>) -> Result<Vec<T>, IntersectError>
+where
+    T: Ord + Copy + Default,
+{
+    check_count(sets.len())?;
+    Ok(intersect::run_svs(&by_size(sets), intersect))
+}
+
+pub fn intersect_bsr<S: BsrState>(
+    set_a: BsrRef<'_, S>,
+    set_b: BsrRef<'_, S>,
+    intersect: Intersect2Bsr<S>) -> Result<BsrVec<S>, IntersectError>
+{
+    check_bsr(set_a)?;
+    check_bsr(set_b)?;
+    let mut result = BsrVec::new();
+    intersect(set_a, set_b, &mut result);
+    Ok(result)
+}
+
+pub fn intersect_k_bsr<'a>(
+    sets: &[BsrRef<'a>],
+    intersect: fn(&[BsrRef<'a>], &mut BsrVec)) -> Result<BsrVec, IntersectError>
+{
+    check_count(sets.len())?;
+    let mut sets = sets.to_vec();
+    for &set in &sets {
+        check_bsr(set)?;
+    }
+    sets.sort_by_key(|s| s.len());
+
+    let mut result = BsrVec::new();
+    intersect(&sets, &mut result);
+    Ok(result)
+}
+
+pub fn svs_bsr<S: BsrState>(
+    sets: &[BsrRef<'_, S>],
+    intersect: Intersect2Bsr<S>) -> Result<BsrVec<S>, IntersectError>
+{
+    check_count(sets.len())?;
+    let mut sets = sets.to_vec();
+    for &set in &sets {
+        check_bsr(set)?;
+    }
+    sets.sort_by_key(|s| s.len());
+
+    let mut left = BsrVec::new();
+    let mut right = BsrVec::new();
+    let result = intersect::svs_bsr(&sets, &mut left, &mut right, intersect);
+    Ok(std::mem::take(result))
+}
+
+#[cfg(feature = "simd")]
+pub fn fesia_from_sorted<F>(set: &SortedSet<i32>, hash_scale: HashScale) -> Result<F, IntersectError>
+where
+    F: SetWithHashScale,
+{
+    if !(hash_scale > 0.0 && hash_scale.is_finite()) {
+        return Err(IntersectError::InvalidHashScale);
+    }
+    Ok(F::from_sorted_scaled(set.as_slice(), hash_scale))
+}
+
+/// Intersects two FESIA sets, intersecting segments with `I`. Either set may
+/// be a `FesiaRef` loaded with `from_bytes`, whose segment table is checked on
+/// load, so a corrupt file gives wrong results rather than reading out of
+/// bounds.
+#[cfg(all(feature = "simd", target_feature = "ssse3"))]
+pub fn fesia_intersect<F, I>(set_a: &F, set_b: &F) -> Vec<i32>
+where
+    F: FesiaIntersect,
+    I: SegmentIntersect,
+{
+    let mut writer = VecWriter::new();
+    set_a.intersect::<VecWriter<i32>, I>(set_b, &mut writer);
+    writer.into()
+}
+
+#[cfg(feature = "simd")]
+pub fn fesia_hash_intersect<F: FesiaIntersect>(set_a: &F, set_b: &F) -> Vec<i32> {
+    let mut writer = VecWriter::new();
+    set_a.hash_intersect(set_b, &mut writer);
+    writer.into()
+}
+
+#[cfg(feature = "simd")]
+pub fn fesia_intersect_k<F>(sets: &[F]) -> Result<Vec<i32>, IntersectError>
+where
+    F: FesiaIntersect + AsRef<F>,
+{
+    check_count(sets.len())?;
+    let mut writer = VecWriter::new();
+    F::intersect_k(sets, &mut writer);
+    Ok(writer.into())
+}
+
+#[cfg(feature = "simd")]
+pub fn fesia_hash_intersect_k<F>(sets: &[F]) -> Result<Vec<i32>, IntersectError>
+where
+    F: FesiaIntersect + AsRef<F>,
+{
+    check_count(sets.len())?;
+    let mut writer = VecWriter::new();
+    F::hash_intersect_k(sets, &mut writer);
+    Ok(writer.into())
+}
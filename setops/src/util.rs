@@ -10,6 +10,20 @@ pub fn slice_i32_to_u32(slice_i32: &[i32]) -> &[u32] {
     }
 }
 
+/// Views 32-bit items, e.g. `u32`, as the `i32` lanes kernels operate on.
+/// Items must be aligned as `i32`, so e.g. `[u8; 4]` is rejected.
+#[inline]
+#[allow(dead_code)]
+pub fn slice_as_i32_mut<T>(slice: &mut [T]) -> &mut [i32] {
+    assert!(std::mem::size_of::<T>() == std::mem::size_of::<i32>());
+    assert!(std::mem::align_of::<T>() >= std::mem::align_of::<i32>());
+    unsafe {
+        std::slice::from_raw_parts_mut(
+            slice.as_mut_ptr() as *mut i32, slice.len()
+        )
+    }
+}
+
 #[inline]
 #[allow(dead_code)]
 pub fn or_16<T: BitOr<T, Output=T> + Copy>(v: [T; 16]) -> T {
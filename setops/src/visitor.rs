@@ -1,4 +1,4 @@
-use crate::{bsr::{BsrVec, BsrRef}, instructions};
+use crate::{bsr::{BsrVec, BsrRef, BsrState}, instructions};
 #[cfg(feature = "simd")]
 use {
     std::simd::*,
@@ -10,6 +10,9 @@ use crate::instructions::{ VEC_SHUFFLE_MASK4, shuffle_epi8 };
 #[cfg(all(feature = "simd", target_feature = "avx2"))]
 use crate::instructions::{VEC_SHUFFLE_MASK8, permutevar8x32_epi32};
 
+mod adapter;
+pub use adapter::*;
+
 /// Used to receive set intersection results in a generic way. Inspired by
 /// roaring-rs.
 pub trait Visitor<T> {
@@ -374,8 +377,8 @@ impl<'a> SimdVisitor16 for SliceWriter<'a, i32> {
 }
 
 /// Allows visiting of single entries in Base and State Representation
-pub trait BsrVisitor {
-    fn visit_bsr(&mut self, base: u32, state: u32);
+pub trait BsrVisitor<S = u32> {
+    fn visit_bsr(&mut self, base: u32, state: S);
 }
 
 /// Allows visiting of multiple entries in Base and State Representation
@@ -390,14 +393,41 @@ pub trait SimdBsrVisitor16 : BsrVisitor {
     fn visit_bsr_vector16(&mut self, base: i32x16, state: i32x16, mask: u64);
 }
 
-impl BsrVisitor for BsrVec {
-    fn visit_bsr(&mut self, base: u32, state: u32) {
+/// Visits the lanes of `base` and `state` selected by `mask` one at a time,
+/// for state widths without vector visitors.
+#[cfg(feature = "simd")]
+#[inline]
+pub fn visit_bsr_lanes<S, V, const LANES: usize>(
+    visitor: &mut V,
+    base: Simd<u32, LANES>,
+    state: Simd<S, LANES>,
+    mut mask: u64)
+where
+    S: BsrState + SimdElement,
+    V: BsrVisitor<S>,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    while mask != 0 {
+        let lane = mask.trailing_zeros() as usize;
+        visitor.visit_bsr(base[lane], state[lane]);
+        mask &= mask - 1;
+    }
+}
+
+impl<S: BsrState> BsrVisitor<S> for BsrVec<S> {
+    fn visit_bsr(&mut self, base: u32, state: S) {
         self.append(base, state)
     }
 }
 
-impl BsrVisitor for Counter {
-    fn visit_bsr(&mut self, _base: u32, state: u32) {
+impl<S: BsrState> Clearable for BsrVec<S> {
+    fn clear(&mut self) {
+        BsrVec::clear(self);
+    }
+}
+
+impl<S: BsrState> BsrVisitor<S> for Counter {
+    fn visit_bsr(&mut self, _base: u32, state: S) {
         self.count += state.count_ones() as usize;
     }
 }
@@ -982,6 +1012,12 @@ impl Into<BsrVec> for UnsafeBsrWriter {
     }
 }
 
+impl Clearable for UnsafeBsrWriter {
+    fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
 impl BsrVisitor for UnsafeBsrWriter {
     fn visit_bsr(&mut self, base: u32, state: u32) {
         unsafe {
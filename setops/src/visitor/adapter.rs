@@ -0,0 +1,610 @@
+/// Visitors which wrap other visitors or closures, so results can be
+/// processed without implementing each visitor trait by hand. Vector visits
+/// are forwarded as vectors where the wrapped visitor accepts them, and
+/// otherwise only the lanes selected by the mask are visited.
+
+#[cfg(feature = "simd")]
+use std::simd::{*, num::{SimdInt, SimdUint}};
+
+use crate::bsr::{BsrState, BsrVec};
+use super::{Visitor, BsrVisitor, Clearable};
+#[cfg(feature = "simd")]
+use super::{
+    SimdVisitor4, SimdVisitor8, SimdVisitor16,
+    SimdBsrVisitor4, SimdBsrVisitor8, SimdBsrVisitor16,
+};
+
+/// Calls a closure with each result: `FnMut(T)` for items or
+/// `FnMut(base, state)` for BSR.
+pub struct FnVisitor<F>(pub F);
+
+impl<T, F: FnMut(T)> Visitor<T> for FnVisitor<F> {
+    fn visit(&mut self, value: T) {
+        (self.0)(value)
+    }
+}
+
+impl<S, F: FnMut(u32, S)> BsrVisitor<S> for FnVisitor<F> {
+    fn visit_bsr(&mut self, base: u32, state: S) {
+        (self.0)(base, state)
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<F: FnMut(i32)> SimdVisitor4 for FnVisitor<F> {
+    fn visit_vector4(&mut self, value: i32x4, mask: u64) {
+        visit_lanes(self, value, mask)
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<F: FnMut(i32)> SimdVisitor8 for FnVisitor<F> {
+    fn visit_vector8(&mut self, value: i32x8, mask: u64) {
+        visit_lanes(self, value, mask)
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<F: FnMut(i32)> SimdVisitor16 for FnVisitor<F> {
+    fn visit_vector16(&mut self, value: i32x16, mask: u64) {
+        visit_lanes(self, value, mask)
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<F: FnMut(u32, u32)> SimdBsrVisitor4 for FnVisitor<F> {
+    fn visit_bsr_vector4(&mut self, base: i32x4, state: i32x4, mask: u64) {
+        visit_bsr_lanes(self, base, state, mask)
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<F: FnMut(u32, u32)> SimdBsrVisitor8 for FnVisitor<F> {
+    fn visit_bsr_vector8(&mut self, base: i32x8, state: i32x8, mask: u64) {
+        visit_bsr_lanes(self, base, state, mask)
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<F: FnMut(u32, u32)> SimdBsrVisitor16 for FnVisitor<F> {
+    fn visit_bsr_vector16(&mut self, base: i32x16, state: i32x16, mask: u64) {
+        visit_bsr_lanes(self, base, state, mask)
+    }
+}
+
+/// Forwards every result to both visitors.
+pub struct Tee<A, B> {
+    pub first: A,
+    pub second: B,
+}
+
+impl<A, B> Tee<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<T: Copy, A: Visitor<T>, B: Visitor<T>> Visitor<T> for Tee<A, B> {
+    fn visit(&mut self, value: T) {
+        self.first.visit(value);
+        self.second.visit(value);
+    }
+}
+
+impl<S: Copy, A: BsrVisitor<S>, B: BsrVisitor<S>> BsrVisitor<S> for Tee<A, B> {
+    fn visit_bsr(&mut self, base: u32, state: S) {
+        self.first.visit_bsr(base, state);
+        self.second.visit_bsr(base, state);
+    }
+}
+
+impl<A: Clearable, B: Clearable> Clearable for Tee<A, B> {
+    fn clear(&mut self) {
+        self.first.clear();
+        self.second.clear();
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<A: SimdVisitor4, B: SimdVisitor4> SimdVisitor4 for Tee<A, B> {
+    fn visit_vector4(&mut self, value: i32x4, mask: u64) {
+        self.first.visit_vector4(value, mask);
+        self.second.visit_vector4(value, mask);
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<A: SimdVisitor8, B: SimdVisitor8> SimdVisitor8 for Tee<A, B> {
+    fn visit_vector8(&mut self, value: i32x8, mask: u64) {
+        self.first.visit_vector8(value, mask);
+        self.second.visit_vector8(value, mask);
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<A: SimdVisitor16, B: SimdVisitor16> SimdVisitor16 for Tee<A, B> {
+    fn visit_vector16(&mut self, value: i32x16, mask: u64) {
+        self.first.visit_vector16(value, mask);
+        self.second.visit_vector16(value, mask);
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<A: SimdBsrVisitor4, B: SimdBsrVisitor4> SimdBsrVisitor4 for Tee<A, B> {
+    fn visit_bsr_vector4(&mut self, base: i32x4, state: i32x4, mask: u64) {
+        self.first.visit_bsr_vector4(base, state, mask);
+        self.second.visit_bsr_vector4(base, state, mask);
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<A: SimdBsrVisitor8, B: SimdBsrVisitor8> SimdBsrVisitor8 for Tee<A, B> {
+    fn visit_bsr_vector8(&mut self, base: i32x8, state: i32x8, mask: u64) {
+        self.first.visit_bsr_vector8(base, state, mask);
+        self.second.visit_bsr_vector8(base, state, mask);
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<A: SimdBsrVisitor16, B: SimdBsrVisitor16> SimdBsrVisitor16 for Tee<A, B> {
+    fn visit_bsr_vector16(&mut self, base: i32x16, state: i32x16, mask: u64) {
+        self.first.visit_bsr_vector16(base, state, mask);
+        self.second.visit_bsr_vector16(base, state, mask);
+    }
+}
+
+/// Forwards only the first `limit` items and drops the rest. Kernels do not
+/// stop early, so this bounds output rather than work.
+pub struct Limit<V> {
+    inner: V,
+    limit: usize,
+    count: usize,
+}
+
+impl<V> Limit<V> {
+    pub fn new(inner: V, limit: usize) -> Self {
+        Self { inner, limit, count: 0 }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.count == self.limit
+    }
+
+    pub fn into_inner(self) -> V {
+        self.inner
+    }
+
+    fn remaining(&self) -> usize {
+        self.limit - self.count
+    }
+}
+
+impl<T, V: Visitor<T>> Visitor<T> for Limit<V> {
+    fn visit(&mut self, value: T) {
+        if !self.is_full() {
+            self.inner.visit(value);
+            self.count += 1;
+        }
+    }
+}
+
+/// Limits items, not states: a state is cut down to its lowest bits when it
+/// holds more items than remain.
+impl<S: BsrState, V: BsrVisitor<S>> BsrVisitor<S> for Limit<V> {
+    fn visit_bsr(&mut self, base: u32, mut state: S) {
+        let mut count = state.count_ones() as usize;
+        while count > self.remaining() {
+            state = state ^ (S::one() << (S::BITS - 1 - state.leading_zeros()) as usize);
+            count -= 1;
+        }
+        if count > 0 {
+            self.inner.visit_bsr(base, state);
+            self.count += count;
+        }
+    }
+}
+
+impl<V: Clearable> Clearable for Limit<V> {
+    fn clear(&mut self) {
+        self.inner.clear();
+        self.count = 0;
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<V: SimdVisitor4> SimdVisitor4 for Limit<V> {
+    fn visit_vector4(&mut self, value: i32x4, mask: u64) {
+        let mask = self.take(mask);
+        self.inner.visit_vector4(value, mask);
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<V: SimdVisitor8> SimdVisitor8 for Limit<V> {
+    fn visit_vector8(&mut self, value: i32x8, mask: u64) {
+        let mask = self.take(mask);
+        self.inner.visit_vector8(value, mask);
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<V: SimdVisitor16> SimdVisitor16 for Limit<V> {
+    fn visit_vector16(&mut self, value: i32x16, mask: u64) {
+        let mask = self.take(mask);
+        self.inner.visit_vector16(value, mask);
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<V: SimdBsrVisitor4> SimdBsrVisitor4 for Limit<V> {
+    fn visit_bsr_vector4(&mut self, base: i32x4, state: i32x4, mask: u64) {
+        if self.fits_bsr(state, mask) {
+            self.inner.visit_bsr_vector4(base, state, mask);
+        }
+        else {
+            visit_bsr_lanes(self, base, state, mask);
+        }
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<V: SimdBsrVisitor8> SimdBsrVisitor8 for Limit<V> {
+    fn visit_bsr_vector8(&mut self, base: i32x8, state: i32x8, mask: u64) {
+        if self.fits_bsr(state, mask) {
+            self.inner.visit_bsr_vector8(base, state, mask);
+        }
+        else {
+            visit_bsr_lanes(self, base, state, mask);
+        }
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<V: SimdBsrVisitor16> SimdBsrVisitor16 for Limit<V> {
+    fn visit_bsr_vector16(&mut self, base: i32x16, state: i32x16, mask: u64) {
+        if self.fits_bsr(state, mask) {
+            self.inner.visit_bsr_vector16(base, state, mask);
+        }
+        else {
+            visit_bsr_lanes(self, base, state, mask);
+        }
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<V> Limit<V> {
+    // Keeps the lowest set bits of `mask`, up to the number of items remaining.
+    fn take(&mut self, mut mask: u64) -> u64 {
+        let mut count = mask.count_ones() as usize;
+        while count > self.remaining() {
+            mask ^= 1 << (63 - mask.leading_zeros());
+            count -= 1;
+        }
+        self.count += count;
+        mask
+    }
+
+    // Counts the items in the selected states, accepting them if they fit.
+    fn fits_bsr<const LANES: usize>(&mut self, state: Simd<i32, LANES>, mask: u64) -> bool
+    where
+        LaneCount<LANES>: SupportedLaneCount,
+    {
+        let count: usize = (0..LANES)
+            .filter(|&lane| mask & (1 << lane) != 0)
+            .map(|lane| state[lane].count_ones() as usize)
+            .sum();
+        let fits = count <= self.remaining();
+        if fits {
+            self.count += count;
+        }
+        fits
+    }
+}
+
+/// Applies `f` to each item before forwarding it, e.g., to turn a matched
+/// value into the index of its payload. For vector visits only the selected
+/// lanes are mapped, then the vector is forwarded.
+pub struct Map<V, F> {
+    inner: V,
+    f: F,
+}
+
+impl<V, F> Map<V, F> {
+    pub fn new(inner: V, f: F) -> Self {
+        Self { inner, f }
+    }
+
+    pub fn into_inner(self) -> V {
+        self.inner
+    }
+}
+
+impl<T, U, V, F> Visitor<T> for Map<V, F>
+where
+    F: FnMut(T) -> U,
+    V: Visitor<U>,
+{
+    fn visit(&mut self, value: T) {
+        self.inner.visit((self.f)(value));
+    }
+}
+
+impl<V: Clearable, F> Clearable for Map<V, F> {
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<V: SimdVisitor4, F: FnMut(i32) -> i32> SimdVisitor4 for Map<V, F> {
+    fn visit_vector4(&mut self, value: i32x4, mask: u64) {
+        let mapped = map_lanes(&mut self.f, value, mask);
+        self.inner.visit_vector4(mapped, mask);
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<V: SimdVisitor8, F: FnMut(i32) -> i32> SimdVisitor8 for Map<V, F> {
+    fn visit_vector8(&mut self, value: i32x8, mask: u64) {
+        let mapped = map_lanes(&mut self.f, value, mask);
+        self.inner.visit_vector8(mapped, mask);
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<V: SimdVisitor16, F: FnMut(i32) -> i32> SimdVisitor16 for Map<V, F> {
+    fn visit_vector16(&mut self, value: i32x16, mask: u64) {
+        let mapped = map_lanes(&mut self.f, value, mask);
+        self.inner.visit_vector16(mapped, mask);
+    }
+}
+
+/// Expands BSR results into the items they represent, in order, so BSR
+/// kernels can feed array kernels' visitors without a `to_sorted_set` pass.
+/// Items are visited as i32, as array kernels produce them.
+pub struct ArrayExpandingVisitor<V> {
+    inner: V,
+}
+
+impl<V> ArrayExpandingVisitor<V> {
+    pub fn new(inner: V) -> Self {
+        Self { inner }
+    }
+
+    pub fn into_inner(self) -> V {
+        self.inner
+    }
+}
+
+impl<S: BsrState, V: Visitor<i32>> BsrVisitor<S> for ArrayExpandingVisitor<V> {
+    fn visit_bsr(&mut self, base: u32, mut state: S) {
+        let high = base << S::SHIFT;
+        while state != S::zero() {
+            self.inner.visit((high | state.trailing_zeros()) as i32);
+            state = state & (state - S::one());
+        }
+    }
+}
+
+impl<V: Clearable> Clearable for ArrayExpandingVisitor<V> {
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<V: SimdVisitor16> SimdBsrVisitor4 for ArrayExpandingVisitor<V> {
+    fn visit_bsr_vector4(&mut self, base: i32x4, state: i32x4, mask: u64) {
+        self.expand_lanes(base, state, mask)
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<V: SimdVisitor16> SimdBsrVisitor8 for ArrayExpandingVisitor<V> {
+    fn visit_bsr_vector8(&mut self, base: i32x8, state: i32x8, mask: u64) {
+        self.expand_lanes(base, state, mask)
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<V: SimdVisitor16> SimdBsrVisitor16 for ArrayExpandingVisitor<V> {
+    fn visit_bsr_vector16(&mut self, base: i32x16, state: i32x16, mask: u64) {
+        self.expand_lanes(base, state, mask)
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<V: SimdVisitor16> ArrayExpandingVisitor<V> {
+    // Each half of a state selects the lanes of a vector holding every item
+    // of that half, so the inner visitor's compressing store does the
+    // expansion, as pdep would.
+    fn expand_lanes<const LANES: usize>(
+        &mut self,
+        base: Simd<i32, LANES>,
+        state: Simd<i32, LANES>,
+        mut mask: u64)
+    where
+        LaneCount<LANES>: SupportedLaneCount,
+    {
+        const OFFSETS: i32x16 = i32x16::from_array([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+
+        while mask != 0 {
+            let lane = mask.trailing_zeros() as usize;
+            let high = i32x16::splat(base[lane] << u32::SHIFT) | OFFSETS;
+            let state = state[lane] as u32;
+
+            if state & 0xFFFF != 0 {
+                self.inner.visit_vector16(high, (state & 0xFFFF) as u64);
+            }
+            if state >> 16 != 0 {
+                self.inner.visit_vector16(high | i32x16::splat(16), (state >> 16) as u64);
+            }
+            mask &= mask - 1;
+        }
+    }
+}
+
+/// Packs sorted items into a `BsrVec` as they are visited, so array kernels
+/// can produce BSR output without a `from_sorted` pass. Items must arrive in
+/// increasing order as u32; i32 items are reinterpreted as u32, so they must
+/// be non-negative, as negative items would sort after all others.
+pub struct BsrBuildingVisitor<S = u32> {
+    bsr: BsrVec<S>,
+}
+
+impl<S: BsrState> BsrBuildingVisitor<S> {
+    pub fn new() -> Self {
+        Self { bsr: BsrVec::new() }
+    }
+
+    pub fn into_inner(self) -> BsrVec<S> {
+        self.bsr
+    }
+
+    // Items arrive in order, so only the last state can share their base.
+    #[inline]
+    fn add(&mut self, base: u32, state: S) {
+        debug_assert!(self.bsr.bases.last().is_none_or(|&last| last <= base),
+            "items must be visited in increasing order as u32");
+        match self.bsr.bases.last() {
+            Some(&last) if last == base => {
+                let last_state = self.bsr.states.last_mut().unwrap();
+                *last_state = *last_state | state;
+            },
+            _ => self.bsr.append(base, state),
+        }
+    }
+}
+
+impl<S: BsrState> Default for BsrBuildingVisitor<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: BsrState> From<BsrBuildingVisitor<S>> for BsrVec<S> {
+    fn from(visitor: BsrBuildingVisitor<S>) -> Self {
+        visitor.bsr
+    }
+}
+
+impl<S: BsrState> Visitor<u32> for BsrBuildingVisitor<S> {
+    fn visit(&mut self, value: u32) {
+        self.add(value >> S::SHIFT, S::bit(value));
+    }
+}
+
+impl<S: BsrState> Visitor<i32> for BsrBuildingVisitor<S> {
+    fn visit(&mut self, value: i32) {
+        Visitor::<u32>::visit(self, value as u32);
+    }
+}
+
+impl<S: BsrState> Clearable for BsrBuildingVisitor<S> {
+    fn clear(&mut self) {
+        self.bsr.clear();
+    }
+}
+
+#[cfg(feature = "simd")]
+impl SimdVisitor4 for BsrBuildingVisitor {
+    fn visit_vector4(&mut self, value: i32x4, mask: u64) {
+        self.pack_lanes(value, mask)
+    }
+}
+
+#[cfg(feature = "simd")]
+impl SimdVisitor8 for BsrBuildingVisitor {
+    fn visit_vector8(&mut self, value: i32x8, mask: u64) {
+        self.pack_lanes(value, mask)
+    }
+}
+
+#[cfg(feature = "simd")]
+impl SimdVisitor16 for BsrBuildingVisitor {
+    fn visit_vector16(&mut self, value: i32x16, mask: u64) {
+        self.pack_lanes(value, mask)
+    }
+}
+
+#[cfg(feature = "simd")]
+impl BsrBuildingVisitor {
+    // Splits all lanes into bases and bits at once. When the selected lanes
+    // share a base, which is usual for dense sets, their bits are ORed into a
+    // single state.
+    fn pack_lanes<const LANES: usize>(&mut self, value: Simd<i32, LANES>, mask: u64)
+    where
+        LaneCount<LANES>: SupportedLaneCount,
+    {
+        if mask == 0 {
+            return;
+        }
+        let items = value.cast::<u32>();
+        let bases = items >> Simd::splat(u32::SHIFT);
+        let bits = Simd::splat(1) << (items & Simd::splat(u32::MASK));
+
+        let first = mask.trailing_zeros() as usize;
+        let last = 63 - mask.leading_zeros() as usize;
+        if bases[first] == bases[last] {
+            let selected = Mask::<i32, LANES>::from_bitmask(mask);
+            self.add(bases[first], selected.select(bits, Simd::splat(0)).reduce_or());
+        }
+        else {
+            let mut mask = mask;
+            while mask != 0 {
+                let lane = mask.trailing_zeros() as usize;
+                self.add(bases[lane], bits[lane]);
+                mask &= mask - 1;
+            }
+        }
+    }
+}
+
+// Visits only the lanes selected by `mask`, lowest first.
+#[cfg(feature = "simd")]
+#[inline]
+fn visit_lanes<V, const LANES: usize>(visitor: &mut V, value: Simd<i32, LANES>, mut mask: u64)
+where
+    V: Visitor<i32>,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    while mask != 0 {
+        visitor.visit(value[mask.trailing_zeros() as usize]);
+        mask &= mask - 1;
+    }
+}
+
+#[cfg(feature = "simd")]
+#[inline]
+fn visit_bsr_lanes<V, const LANES: usize>(
+    visitor: &mut V,
+    base: Simd<i32, LANES>,
+    state: Simd<i32, LANES>,
+    mask: u64)
+where
+    V: BsrVisitor,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    super::visit_bsr_lanes(visitor, base.cast::<u32>(), state.cast::<u32>(), mask)
+}
+
+// Maps the lanes selected by `mask`, leaving the others zero.
+#[cfg(feature = "simd")]
+#[inline]
+fn map_lanes<F, const LANES: usize>(f: &mut F, value: Simd<i32, LANES>, mut mask: u64) -> Simd<i32, LANES>
+where
+    F: FnMut(i32) -> i32,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    let mut mapped = Simd::splat(0);
+    while mask != 0 {
+        let lane = mask.trailing_zeros() as usize;
+        mapped[lane] = f(value[lane]);
+        mask &= mask - 1;
+    }
+    mapped
+}
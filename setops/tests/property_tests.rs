@@ -5,14 +5,26 @@ extern crate quickcheck;
 mod testlib;
 use testlib::{
     DualIntersectFn, SortedSet, SetCollection,
-    properties::prop_intersection_correct,
-    SimilarSetPair, SkewedSetPair,
+    properties::{prop_intersection_correct, prop_multiset_intersection_correct, prop_bag_union_correct},
+    SimilarSetPair, SkewedSetPair, MultisetPair,
 };
 use setops::{
-    intersect::{self, fesia::*, Intersect2}, bsr::BsrVec, Set,
-    visitor::{VecWriter, UnsafeWriter, EnsureVisitor, EnsureVisitorBsr, Counter},
+    intersect::{
+        self, fesia::*, probe::*, Intersect2,
+        GroupHashSet, GroupHashSetSse, GroupHashSetAvx2, GroupHashSetAvx512,
+    }, bsr::{self, BsrVec, Intersect2BsrV}, Set,
+    Cardinality, ToSorted,
+    graph::{self, CsrGraph},
+    estimate::{self, Estimate, KmvSketch},
+    safe,
+    visitor::{
+        VecWriter, UnsafeWriter, EnsureVisitor, EnsureVisitorBsr, Counter,
+        FnVisitor, Tee, Limit, Map, ArrayExpandingVisitor, BsrBuildingVisitor,
+    },
 };
 
+use std::{collections::{BTreeSet, HashSet}, simd::{*, cmp::*}, ops::BitAnd};
+
 use FesiaTwoSetMethod::*;
 use SimdType::*;
 
@@ -80,7 +92,7 @@ quickcheck! {
     }
 
     fn branchless_merge_bsr_correct(sets: SimilarSetPair<u32>) -> bool {
-        let left = BsrVec::from_sorted(sets.0.as_ref());
+        let left: BsrVec = BsrVec::from_sorted(sets.0.as_ref());
         let right = BsrVec::from_sorted(sets.1.as_ref());
 
         let expected = intersect::run_2set(
@@ -146,7 +158,7 @@ quickcheck! {
 
     #[cfg(feature = "simd")]
     fn shuffling_sse_bsr_correct(sets: SimilarSetPair<u32>) -> bool {
-        let left = BsrVec::from_sorted(sets.0.as_ref());
+        let left: BsrVec = BsrVec::from_sorted(sets.0.as_ref());
         let right = BsrVec::from_sorted(sets.1.as_ref());
 
         let expected = intersect::run_2set_bsr(
@@ -160,7 +172,7 @@ quickcheck! {
 
     #[cfg(all(feature = "simd", target_feature = "avx2"))]
     fn shuffling_avx2_bsr_correct(sets: SimilarSetPair<u32>) -> bool {
-        let left = BsrVec::from_sorted(sets.0.as_ref());
+        let left: BsrVec = BsrVec::from_sorted(sets.0.as_ref());
         let right = BsrVec::from_sorted(sets.1.as_ref());
 
         let expected = intersect::run_2set_bsr(
@@ -173,7 +185,7 @@ quickcheck! {
 
     #[cfg(all(feature = "simd", target_feature = "avx512f"))]
     fn shuffling_avx512_bsr_correct(sets: SimilarSetPair<u32>) -> bool {
-        let left = BsrVec::from_sorted(sets.0.as_ref());
+        let left: BsrVec = BsrVec::from_sorted(sets.0.as_ref());
         let right = BsrVec::from_sorted(sets.1.as_ref());
 
         let expected = intersect::run_2set_bsr(
@@ -187,7 +199,7 @@ quickcheck! {
 
     #[cfg(all(feature = "simd", target_feature = "avx512f"))]
     fn broadcast_avx512_bsr_correct(sets: SimilarSetPair<u32>) -> bool {
-        let left = BsrVec::from_sorted(sets.0.as_ref());
+        let left: BsrVec = BsrVec::from_sorted(sets.0.as_ref());
         let right = BsrVec::from_sorted(sets.1.as_ref());
 
         let expected = intersect::run_2set_bsr(
@@ -199,6 +211,477 @@ quickcheck! {
         actual == expected
     }
 
+    fn bsr_ops_correct(sets: SimilarSetPair<u32>, dense: bool) -> bool {
+        // Small items give several items per state.
+        let items = |set: &SortedSet<u32>| -> Vec<u32> {
+            set.as_slice().iter().map(|&i| if dense { i % 2048 } else { i }).collect()
+        };
+        let left: BsrVec = items(&sets.0).into_iter().collect();
+        let right: BsrVec = items(&sets.1).into_iter().collect();
+
+        let mut kernels: Vec<bsr::MatchStates> = vec![bsr::match_states_scalar];
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        kernels.push(bsr::match_states_sse);
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        kernels.push(bsr::match_states_avx2);
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        kernels.push(bsr::match_states_avx512);
+
+        kernels.into_iter().all(|kernel| {
+            bsr_ops(&left, &right, kernel) && bsr_ops(&right, &left, kernel)
+        })
+    }
+
+    fn bsr_kset_correct(sets: SetCollection<u32>, dense: bool) -> bool {
+        let mut bsr_sets: Vec<BsrVec> = sets.as_slice().iter()
+            .map(|set| set.as_slice().iter().map(|&i| if dense { i % 4096 } else { i }).collect())
+            .collect();
+        bsr_sets.sort_by_key(|s| s.len());
+        let arrays: Vec<Vec<u32>> = bsr_sets.iter().map(|s| s.to_sorted_set()).collect();
+        let refs: Vec<_> = bsr_sets.iter().map(|s| s.bsr_ref()).collect();
+
+        let expected = BsrVec::from_sorted(&intersect::run_svs(&arrays, intersect::naive_merge));
+
+        let mut left = BsrVec::new();
+        let mut right = BsrVec::new();
+        let svs = intersect::svs_bsr(&refs, &mut left, &mut right, intersect::branchless_merge_bsr);
+        let svs_correct = *svs == expected;
+
+        let mut small_adaptive = BsrVec::new();
+        intersect::small_adaptive_bsr(&refs, &mut small_adaptive);
+
+        let mut merge_k = BsrVec::new();
+        intersect::merge_k_bsr(&refs, &mut merge_k);
+
+        svs_correct && small_adaptive == expected && merge_k == expected
+    }
+
+    fn bsr_conversions_correct(items: Vec<u32>) -> bool {
+        let items: Vec<u32> = items.into_iter().map(|i| i % 4096).collect();
+        let expected: BTreeSet<u32> = items.iter().copied().collect();
+        let bsr: BsrVec = items.into_iter().collect();
+
+        bsr.to_sorted_set() == expected.iter().copied().collect::<Vec<_>>() &&
+        bsr.cardinality() == expected.len() &&
+        (0..4200).all(|i| bsr.contains(i) == expected.contains(&i)) &&
+        BsrVec::from_bitmap(&bsr.to_bitmap()) == bsr
+    }
+
+    fn bsr64_correct(sets: SimilarSetPair<u32>, dense: bool) -> bool {
+        let items = |set: &SortedSet<u32>| -> Vec<u32> {
+            set.as_slice().iter().map(|&i| if dense { i % 4096 } else { i }).collect()
+        };
+        let left: BsrVec = items(&sets.0).into_iter().collect();
+        let right: BsrVec = items(&sets.1).into_iter().collect();
+        let left64 = BsrVec::<u64>::from(&left);
+        let right64 = BsrVec::<u64>::from(&right);
+
+        let expected = intersect::run_2set_bsr(
+            left.bsr_ref(), right.bsr_ref(), intersect::branchless_merge_bsr);
+
+        let mut kernels: Vec<bsr::Intersect2Bsr<u64>> = vec![intersect::branchless_merge_bsr];
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        kernels.extend([intersect::shuffling_avx2_bsr64, intersect::broadcast_avx2_bsr64]);
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        kernels.extend([intersect::shuffling_avx512_bsr64, intersect::broadcast_avx512_bsr64]);
+
+        let roundtrip = BsrVec::<u32>::from(&left64) == left &&
+            left64.to_sorted_set() == left.to_sorted_set() &&
+            BsrVec::<u64>::from_sorted(&left.to_sorted_set()) == left64;
+
+        roundtrip && kernels.into_iter().all(|kernel| {
+            let actual = intersect::run_2set_bsr(left64.bsr_ref(), right64.bsr_ref(), kernel);
+            BsrVec::<u32>::from(&actual) == expected
+        })
+    }
+
+    fn safe_sorted_set_correct(items: Vec<i32>) -> bool {
+        let strictly_sorted = items.windows(2).all(|w| w[0] < w[1]);
+        let from_unsorted = safe::SortedSet::from_unsorted(items.clone());
+        let expected: Vec<i32> = items.iter().copied().collect::<BTreeSet<_>>().into_iter().collect();
+
+        safe::SortedSet::new(items).is_ok() == strictly_sorted &&
+        from_unsorted.as_slice() == expected &&
+        safe::SortedSet::new(from_unsorted.into_inner()).is_ok()
+    }
+
+    fn safe_intersect_correct(sets: SetCollection<i32>) -> bool {
+        let sets: Vec<safe::SortedSet<i32>> = sets.as_slice().iter()
+            .map(|set| safe::SortedSet::new(set.as_slice().to_vec()).unwrap())
+            .collect();
+        let arrays: Vec<&[i32]> = sets.iter().map(|s| s.as_slice()).collect();
+        let expected = intersect::run_svs(&arrays, intersect::naive_merge);
+
+        let pair = safe::intersect(&sets[0], &sets[1], intersect::branchless_merge);
+        let pair_count = safe::count(&sets[0], &sets[1], intersect::branchless_merge);
+
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        let pair_c_correct = {
+            let mut out = vec![0; sets[0].len().min(sets[1].len()) + intersect::RESULT_SLACK];
+            let written = safe::intersect_c_into(&sets[0], &sets[1], intersect::qfilter_c, &mut out);
+            let correct = written.map(|n| out[..n] == pair) == Ok(true);
+            correct &&
+                safe::intersect_c_into(&sets[0], &sets[1], intersect::qfilter_c, &mut out[1..]).is_err()
+        };
+        #[cfg(not(all(feature = "simd", target_feature = "ssse3")))]
+        let pair_c_correct = true;
+
+        pair_count == pair.len() && pair_c_correct &&
+        safe::intersect_k(&sets, intersect::small_adaptive).unwrap() == expected &&
+        safe::svs(&sets, intersect::galloping).unwrap() == expected &&
+        safe::intersect_k(&sets[..1], intersect::small_adaptive).is_err()
+    }
+
+    fn safe_bsr_correct(sets: SetCollection<u32>) -> bool {
+        let bsr_sets: Vec<BsrVec> = sets.as_slice().iter()
+            .map(|set| BsrVec::from_sorted(set.as_slice()))
+            .collect();
+        let refs: Vec<_> = bsr_sets.iter().map(|s| s.bsr_ref()).collect();
+        let arrays: Vec<&[u32]> = sets.as_slice().iter().map(|s| s.as_slice()).collect();
+        let expected = BsrVec::from_sorted(&intersect::run_svs(&arrays, intersect::naive_merge));
+
+        let mut broken = bsr_sets[0].clone();
+        broken.states.push(1);
+
+        safe::svs_bsr(&refs, intersect::branchless_merge_bsr).unwrap() == expected &&
+        safe::intersect_k_bsr(&refs, intersect::small_adaptive_bsr).unwrap() == expected &&
+        safe::intersect_bsr(broken.bsr_ref(), refs[1], intersect::branchless_merge_bsr).is_err()
+    }
+
+    fn set_traits_correct(sets: SimilarSetPair<i32>) -> bool {
+        // Non-negative so that items keep their order as u32.
+        let non_negative = |s: &[i32]| -> Vec<i32> { s.iter().copied().filter(|&i| i >= 0).collect() };
+        let (a, b) = (&non_negative(sets.0.as_slice())[..], &non_negative(sets.1.as_slice())[..]);
+        let expected = intersect::run_2set(a, b, intersect::naive_merge);
+        let unsigned = |s: &[i32]| -> Vec<u32> { s.iter().map(|&i| i as u32).collect() };
+        let expected_u32 = unsigned(&expected);
+
+        let mut correct =
+            set_traits::<i32, Vec<i32>>(a, b, &expected) &&
+            set_traits::<i32, HashSet<i32>>(a, b, &expected) &&
+            set_traits::<i32, BTreeSet<i32>>(a, b, &expected) &&
+            set_traits::<i32, safe::SortedSet<i32>>(a, b, &expected) &&
+            set_traits::<u32, BsrVec>(&unsigned(a), &unsigned(b), &expected_u32) &&
+            set_traits::<u32, BsrVec<u64>>(&unsigned(a), &unsigned(b), &expected_u32);
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        {
+            correct = correct &&
+                set_traits::<i32, Fesia8Sse>(a, b, &expected) &&
+                set_traits::<i32, Fesia32Sse>(a, b, &expected);
+        }
+
+        let bsr: BsrVec = BsrVec::from_set(&BTreeSet::from_sorted(&expected_u32));
+        let btree: BTreeSet<u32> = BTreeSet::from_set(&bsr);
+        correct && bsr.to_sorted() == expected_u32 && btree.into_iter().eq(expected_u32)
+    }
+
+    fn context_correct(
+        intersect: DualIntersectFn,
+        queries: Vec<SetCollection<i32>>) -> bool
+    {
+        // One context is reused across queries, as on a worker thread.
+        let mut context = intersect::IntersectionContext::new();
+        queries.iter().all(|sets| {
+            let sets = sets.as_slice();
+            let expected = intersect::run_svs(sets, intersect::naive_merge);
+            let pair = intersect::run_2set(sets[0].as_slice(), sets[1].as_slice(), intersect.1);
+            // More sets than small_adaptive keeps inline.
+            let many_sets: Vec<&[i32]> = sets.iter().cycle().take(12)
+                .map(|set| set.as_slice()).collect();
+
+            #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+            let svs_c_correct = context.run_svs_c(sets, intersect::qfilter_c) == expected;
+            #[cfg(not(all(feature = "simd", target_feature = "ssse3")))]
+            let svs_c_correct = true;
+
+            svs_c_correct &&
+            context.run_2set(sets[0].as_slice(), sets[1].as_slice(), intersect.1) == pair &&
+            context.run_kset(sets, intersect::small_adaptive) == expected &&
+            context.run_small_adaptive(sets) == expected &&
+            context.run_small_adaptive(&many_sets) == expected &&
+            context.run_svs(sets, intersect.1) == expected &&
+            context.run_svs_inplace(sets, intersect::galloping_inplace) == expected
+        })
+    }
+
+    fn inplace_correct(similar: SimilarSetPair<i32>, skewed: SkewedSetPair<i32>) -> bool {
+        let pairs = [
+            (similar.0.as_slice(), similar.1.as_slice()),
+            (skewed.small.as_slice(), skewed.large.as_slice()),
+            (skewed.large.as_slice(), skewed.small.as_slice()),
+        ];
+        pairs.iter().all(|&(set_a, set_b)| inplace_kernels().iter().all(|&intersect| {
+            let mut result = set_a.to_vec();
+            let count = intersect(&mut result, set_b);
+            result[..count] == intersect::run_2set(set_a, set_b, intersect::naive_merge)
+        }))
+    }
+
+    fn svs_inplace_correct(sets: SetCollection<i32>) -> bool {
+        let sets = sets.as_slice();
+        let expected = intersect::run_svs(sets, intersect::naive_merge);
+        inplace_kernels().iter().all(|&intersect| {
+            let mut out = vec![0; sets[0].as_slice().len()];
+            let count = intersect::svs_generic_inplace(sets, &mut out, intersect);
+            out[..count] == expected
+        })
+    }
+
+    fn visitor_adapters_correct(sets: SimilarSetPair<i32>, limit: usize) -> bool {
+        let (set_a, set_b) = (sets.0.as_slice(), sets.1.as_slice());
+        let expected = intersect::run_2set(set_a, set_b, intersect::naive_merge);
+        let limit = limit % (expected.len() + 2);
+        let limited = &expected[..limit.min(expected.len())];
+        let mapped: Vec<i32> = expected.iter().map(|&i| i / 2).collect();
+
+        let mut correct = true;
+        let mut items: Vec<i32> = Vec::new();
+        intersect::branchless_merge(set_a, set_b, &mut FnVisitor(|i| items.push(i)));
+        correct &= items == expected;
+
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        {
+            let mut items: Vec<i32> = Vec::new();
+            intersect::shuffling_sse(set_a, set_b, &mut FnVisitor(|i| items.push(i)));
+            correct &= items == expected;
+
+            let mut tee = Tee::new(VecWriter::<i32>::new(), Counter::new());
+            intersect::shuffling_sse(set_a, set_b, &mut tee);
+            let (writer, counter) = tee.into_inner();
+            correct &= Vec::from(writer) == expected && counter.count() == expected.len();
+
+            let mut limit_visitor = Limit::new(VecWriter::<i32>::new(), limit);
+            intersect::shuffling_sse(set_a, set_b, &mut limit_visitor);
+            correct &= Vec::from(limit_visitor.into_inner()) == limited;
+
+            let mut map = Map::new(VecWriter::<i32>::new(), |i: i32| i / 2);
+            intersect::shuffling_sse(set_a, set_b, &mut map);
+            correct &= Vec::from(map.into_inner()) == mapped;
+        }
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        {
+            let mut limit_visitor = Limit::new(VecWriter::<i32>::new(), limit);
+            intersect::shuffling_avx512(set_a, set_b, &mut limit_visitor);
+            correct &= Vec::from(limit_visitor.into_inner()) == limited;
+
+            let mut map = Map::new(VecWriter::<i32>::new(), |i: i32| i / 2);
+            intersect::shuffling_avx512(set_a, set_b, &mut map);
+            correct &= Vec::from(map.into_inner()) == mapped;
+        }
+
+        let mut limit_visitor = Limit::new(VecWriter::<i32>::new(), limit);
+        intersect::branchless_merge(set_a, set_b, &mut limit_visitor);
+        correct && Vec::from(limit_visitor.into_inner()) == limited
+    }
+
+    fn bsr_visitor_adapters_correct(sets: SimilarSetPair<u32>, limit: usize, dense: bool) -> bool {
+        let items = |set: &SortedSet<u32>| -> BsrVec {
+            set.as_slice().iter().map(|&i| if dense { i % 2048 } else { i }).collect()
+        };
+        let (left, right) = (items(&sets.0), items(&sets.1));
+        let expected = intersect::run_2set_bsr(left.bsr_ref(), right.bsr_ref(), intersect::branchless_merge_bsr)
+            .to_sorted_set();
+        let limit = limit % (expected.len() + 2);
+        let limited = &expected[..limit.min(expected.len())];
+
+        let mut correct = true;
+        let mut kernels: Vec<Intersect2BsrV<ArrayExpandingVisitor<VecWriter<u32>>>> =
+            vec![intersect::branchless_merge_bsr];
+        let mut limit_kernels: Vec<Intersect2BsrV<Limit<BsrVec>>> =
+            vec![intersect::branchless_merge_bsr];
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        {
+            kernels.push(intersect::shuffling_sse_bsr);
+            limit_kernels.push(intersect::shuffling_sse_bsr);
+        }
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        {
+            kernels.push(intersect::shuffling_avx512_bsr);
+            limit_kernels.push(intersect::shuffling_avx512_bsr);
+        }
+        for kernel in kernels {
+            let mut visitor = ArrayExpandingVisitor::new(VecWriter::<u32>::new());
+            kernel(left.bsr_ref(), right.bsr_ref(), &mut visitor);
+            correct &= Vec::from(visitor.into_inner()) == expected;
+        }
+        for kernel in limit_kernels {
+            let mut visitor = Limit::new(BsrVec::new(), limit);
+            kernel(left.bsr_ref(), right.bsr_ref(), &mut visitor);
+            correct &= visitor.into_inner().to_sorted_set() == limited;
+        }
+        correct
+    }
+
+    fn switch_representation_correct(sets: SimilarSetPair<i32>, dense: bool) -> bool {
+        // Items are made non-negative so that they sort the same as u32.
+        let items = |set: &SortedSet<i32>| -> Vec<i32> {
+            let items = set.as_slice().iter().map(|&i| if dense { i & 0x7FF } else { i & i32::MAX });
+            SortedSet::from_unsorted(items.collect()).into_inner()
+        };
+        let (set_a, set_b) = (items(&sets.0), items(&sets.1));
+        let expected = intersect::run_2set(&set_a, &set_b, intersect::naive_merge);
+        let expected_u32: Vec<u32> = expected.iter().map(|&i| i as u32).collect();
+        let expected_bsr: BsrVec = BsrVec::from_sorted(&expected_u32);
+
+        let bsr_a: BsrVec = set_a.iter().map(|&i| i as u32).collect();
+        let bsr_b: BsrVec = set_b.iter().map(|&i| i as u32).collect();
+
+        let mut to_bsr: Vec<Intersect2<[i32], BsrBuildingVisitor>> = vec![intersect::branchless_merge];
+        let mut to_array: Vec<Intersect2BsrV<ArrayExpandingVisitor<VecWriter<i32>>>> =
+            vec![intersect::branchless_merge_bsr];
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        {
+            to_bsr.extend([intersect::shuffling_sse, intersect::broadcast_sse]);
+            to_array.extend([intersect::shuffling_sse_bsr, intersect::broadcast_sse_bsr]);
+        }
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        {
+            to_bsr.extend([intersect::shuffling_avx2, intersect::broadcast_avx2]);
+            to_array.extend([intersect::shuffling_avx2_bsr, intersect::broadcast_avx2_bsr]);
+        }
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        {
+            to_bsr.extend([intersect::shuffling_avx512, intersect::broadcast_avx512]);
+            to_array.extend([intersect::shuffling_avx512_bsr, intersect::broadcast_avx512_bsr]);
+        }
+
+        let bsr_correct = to_bsr.iter().all(|intersect| {
+            let mut visitor = BsrBuildingVisitor::new();
+            intersect(&set_a, &set_b, &mut visitor);
+            BsrVec::from(visitor) == expected_bsr
+        });
+        let array_correct = to_array.iter().all(|intersect| {
+            let mut visitor = ArrayExpandingVisitor::new(VecWriter::new());
+            intersect(bsr_a.bsr_ref(), bsr_b.bsr_ref(), &mut visitor);
+            Vec::from(visitor.into_inner()) == expected
+        });
+        bsr_correct && array_correct
+    }
+
+    fn unsigned_range_correct(similar: SimilarSetPair<u32>, skewed: SkewedSetPair<u32>) -> bool {
+        // Items straddle 2^31, where u32 and i32 orders differ, sit at the
+        // top of the range, or are spread over the whole range.
+        let map = |set: &SortedSet<u32>, f: fn(u32) -> u32| -> Vec<u32> {
+            SortedSet::from_unsorted(set.as_slice().iter().map(|&i| f(i)).collect()).into_inner()
+        };
+        let high: fn(u32) -> u32 = |i| (i % 8192).wrapping_add((1 << 31) - 4096);
+        let top: fn(u32) -> u32 = |i| u32::MAX - i % 8192;
+        let spread: fn(u32) -> u32 = |i| i.wrapping_mul(0x9E37_79B9);
+        let mut pairs = Vec::new();
+        for f in [high, top, spread] {
+            pairs.push((map(&similar.0, f), map(&similar.1, f)));
+            pairs.push((map(&skewed.small, f), map(&skewed.large, f)));
+            pairs.push((map(&skewed.large, f), map(&skewed.small, f)));
+        }
+        pairs.push((similar.0.into_inner(), similar.1.into_inner()));
+        pairs.iter().all(|(set_a, set_b)| {
+            let expected = intersect::run_2set(set_a, set_b, intersect::naive_merge);
+            let kernels_correct = unsigned_kernels().iter().all(|&intersect| {
+                intersect::run_2set(set_a, set_b, intersect) == expected
+            });
+            let inplace_correct = inplace_kernels::<u32>().iter().all(|&intersect| {
+                let mut result = set_a.clone();
+                let count = intersect(&mut result, set_b);
+                result[..count] == expected
+            });
+            #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+            let c_correct =
+                intersect::run_2set_c(set_a, set_b, intersect::qfilter_c) == expected &&
+                intersect::run_2set_c(set_a, set_b, intersect::mono::qfilter_c_mono_u32) == expected;
+            #[cfg(not(all(feature = "simd", target_feature = "ssse3")))]
+            let c_correct = true;
+
+            #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+            let fesia_correct = {
+                let signed = |set: &[u32]| -> Vec<i32> {
+                    set.iter().map(|&i| to_signed_order(i)).collect()
+                };
+                let fesia_a = Fesia32Sse::from_sorted_scaled(&signed(set_a), 1.0);
+                let fesia_b = Fesia32Sse::from_sorted_scaled(&signed(set_b), 1.0);
+                let mut actual: Vec<u32> = Vec::new();
+                fesia_a.intersect::<_, SegmentIntersectSse>(
+                    &fesia_b, &mut FnVisitor(|i| actual.push(from_signed_order(i))));
+                actual.sort();
+                actual == expected &&
+                fesia_a.to_sorted_set().into_iter().map(from_signed_order).eq(set_a.iter().copied())
+            };
+            #[cfg(not(all(feature = "simd", target_feature = "ssse3")))]
+            let fesia_correct = true;
+
+            let hash_set: HashSet<u32> = set_b.iter().copied().collect();
+            let group_set: GroupHashSetAvx2<u32> = GroupHashSet::from_sorted(set_b);
+            let probes_correct =
+                run_mixed(set_a, &hash_set, intersect::probe_array_hash_set) == expected &&
+                run_mixed(set_a, &group_set, intersect::probe_array_group_hash_set) == expected;
+
+            let mut sets = [set_a.as_slice(), set_b.as_slice()];
+            sets.sort_by_key(|set| set.len());
+            let kset_correct =
+                intersect::run_kset(&sets, intersect::small_adaptive) == expected &&
+                intersect::run_kset(&sets, intersect::baezayates_k) == expected &&
+                intersect::run_svs(&sets, intersect::galloping) == expected;
+
+            let bsr_a: BsrVec = BsrVec::from_sorted(set_a);
+            let bsr_b: BsrVec = BsrVec::from_sorted(set_b);
+            let bsr_correct = intersect::run_2set_bsr(
+                bsr_a.bsr_ref(), bsr_b.bsr_ref(), intersect::branchless_merge_bsr)
+                .to_sorted_set() == expected;
+
+            kernels_correct && inplace_correct && c_correct && fesia_correct &&
+            probes_correct && kset_correct && bsr_correct
+        })
+    }
+
+    fn mixed_correct(sets: SkewedSetPair<i32>, dense: bool) -> bool {
+        // Items are made non-negative so that they sort the same as u32.
+        let items = |set: &SortedSet<i32>| -> Vec<i32> {
+            let items = set.as_slice().iter().map(|&i| if dense { i & 0xFFF } else { i & i32::MAX });
+            SortedSet::from_unsorted(items.collect()).into_inner()
+        };
+        let small = items(&sets.small);
+        let large = items(&sets.large);
+        let expected = intersect::run_2set(&small, &large, intersect::naive_merge);
+
+        let small_u32: Vec<u32> = small.iter().map(|&i| i as u32).collect();
+        let large_u32: Vec<u32> = large.iter().map(|&i| i as u32).collect();
+        let expected_u32: Vec<u32> = expected.iter().map(|&i| i as u32).collect();
+
+        let bsr: BsrVec = BsrVec::from_sorted(&large_u32);
+        let bsr64: BsrVec<u64> = BsrVec::from_sorted(&large_u32);
+        let hash_set: HashSet<i32> = large.iter().copied().collect();
+
+        let mut correct =
+            run_mixed(&small_u32, &bsr.bsr_ref(), |s, l, v| intersect::galloping_array_bsr(s, *l, v)) == expected_u32 &&
+            run_mixed(&small_u32, &bsr64.bsr_ref(), |s, l, v| intersect::galloping_array_bsr(s, *l, v)) == expected_u32 &&
+            run_mixed(&small, &hash_set, intersect::probe_array_hash_set) == expected;
+
+        #[cfg(feature = "simd")]
+        {
+            let fesia = Fesia8Sse::from_sorted_scaled(&large, 1.0);
+            let group_sse: GroupHashSetSse<i32> = GroupHashSet::from_sorted(&large);
+            let group_avx512: GroupHashSetAvx512<i32> = GroupHashSet::from_sorted(&large);
+            correct = correct &&
+                run_mixed(&small, &fesia.fesia_ref(), |s, l, v| intersect::probe_array_fesia(s, *l, v)) == expected &&
+                run_mixed(&small, &group_sse, intersect::probe_array_group_hash_set) == expected &&
+                run_mixed(&small, &group_avx512, intersect::probe_array_group_hash_set) == expected;
+        }
+        correct
+    }
+
+    #[cfg(feature = "simd")]
+    fn group_hash_set_correct(items: Vec<i32>, probes: Vec<i32>) -> bool {
+        // i32::MIN has the bits of a free slot.
+        let mut set: GroupHashSetAvx2<i32> = GroupHashSet::new();
+        let mut reference: HashSet<i32> = HashSet::new();
+        let inserts_correct = items.iter().chain(&[i32::MIN, i32::MIN]).all(|&item| {
+            set.insert(item) == reference.insert(item)
+        });
+        inserts_correct &&
+        set.len() == reference.len() &&
+        set.cardinality() == reference.len() &&
+        items.iter().chain(&probes).all(|&item| set.contains(item) == reference.contains(&item))
+    }
+
     #[cfg(feature = "simd")]
     fn broadcast_sse_correct(set_a: SortedSet<i32>, set_b: SortedSet<i32>) -> bool {
         let result = intersect::run_2set(
@@ -523,7 +1006,7 @@ quickcheck! {
 
     #[cfg(feature = "simd")]
     fn qfilter_bsr_correct(sets: SimilarSetPair<u32>) -> bool {
-        let left = BsrVec::from_sorted(sets.0.as_ref());
+        let left: BsrVec = BsrVec::from_sorted(sets.0.as_ref());
         let right = BsrVec::from_sorted(sets.1.as_ref());
 
         let expected = intersect::run_2set_bsr(
@@ -537,7 +1020,7 @@ quickcheck! {
 
     #[cfg(feature = "simd")]
     fn qfilter_bsr_counter_correct(sets: SimilarSetPair<u32>) -> bool {
-        let left = BsrVec::from_sorted(sets.0.as_ref());
+        let left: BsrVec = BsrVec::from_sorted(sets.0.as_ref());
         let right = BsrVec::from_sorted(sets.1.as_ref());
 
         let mut expected = Counter::new();
@@ -682,6 +1165,21 @@ quickcheck! {
         })
     }
 
+    #[cfg(feature = "simd")]
+    fn fesia_kernels_correct(sets: SimilarSetPair<i32>) -> bool {
+        // Multiples of the hash size all land in the first segment under
+        // IdentityHash, giving segments larger than any kernel instantiation.
+        let spread = |set: &SortedSet<i32>| {
+            let mut items: Vec<i32> = set.as_slice().iter().map(|&i| i.wrapping_mul(512)).collect();
+            items.sort();
+            items.dedup();
+            items
+        };
+
+        fesia_kernels::<MixHash>(sets.0.as_slice(), sets.1.as_slice(), 4.0) &&
+        fesia_kernels::<IdentityHash>(&spread(&sets.0), &spread(&sets.1), 0.0)
+    }
+
     #[cfg(all(feature = "simd", target_feature = "ssse3"))]
     fn fesia_kset_sse_correct(sets: SetCollection<i32>) -> bool {
         let mut sets: Vec<SortedSet<i32>> = sets.into();
@@ -715,6 +1213,176 @@ quickcheck! {
         })
     }
 
+    #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+    fn fesia_persist_correct(sets: SetCollection<i32>) -> bool {
+        let mut sets: Vec<SortedSet<i32>> = sets.into();
+        sets.sort_by_key(|s| s.as_slice().len());
+        let hash_scale = 4.0;
+
+        let fesia_sets: Vec<Fesia32Sse> = sets.iter()
+            .map(|s| Fesia32Sse::from_sorted_scaled(s.as_slice(), hash_scale))
+            .collect();
+        let buffers: Vec<Vec<i32>> = fesia_sets.iter().map(persist_aligned).collect();
+        let loaded: Vec<FesiaRef<MixHash, i32, 4>> = buffers.iter()
+            .map(|b| FesiaRef::from_bytes(aligned_bytes(b)).unwrap())
+            .collect();
+
+        let mut expected: VecWriter<i32> = VecWriter::new();
+        let mut actual: VecWriter<i32> = VecWriter::new();
+        fesia_sets[0].intersect::<VecWriter<i32>, SegmentIntersectSse>(&fesia_sets[1], &mut expected);
+        loaded[0].intersect::<VecWriter<i32>, SegmentIntersectSse>(&loaded[1], &mut actual);
+        let twoset_same = expected.as_ref() == actual.as_ref();
+
+        let mut expected: VecWriter<i32> = VecWriter::new();
+        let mut actual: VecWriter<i32> = VecWriter::new();
+        fesia_sets[0].hash_intersect(&fesia_sets[1], &mut expected);
+        loaded[0].hash_intersect(&loaded[1], &mut actual);
+        let hash_same = expected.as_ref() == actual.as_ref();
+
+        let mut expected: VecWriter<i32> = VecWriter::new();
+        let mut actual: VecWriter<i32> = VecWriter::new();
+        Fesia32Sse::intersect_k(&fesia_sets, &mut expected);
+        FesiaRef::intersect_k(&loaded, &mut actual);
+        let kset_same = expected.as_ref() == actual.as_ref();
+
+        let wrong_type = FesiaRef::<MixHash, i16, 8>::from_bytes(aligned_bytes(&buffers[0]))
+            .is_err();
+
+        twoset_same && hash_same && kset_same && wrong_type &&
+        loaded.iter().zip(&sets).all(|(l, s)| l.to_sorted_set() == s.as_slice())
+    }
+
+    #[cfg(feature = "simd")]
+    fn fesia_persist_rejects_corrupt(set: SortedSet<i32>, cut: usize, segment: usize) -> bool {
+        let fesia = Fesia32Sse::from_sorted_scaled(set.as_slice(), 1.0);
+        let buffer = persist_aligned(&fesia);
+        let load = |buffer: &[i32]|
+            FesiaRef::<MixHash, i32, 4>::from_bytes(aligned_bytes(buffer)).map(|_| ());
+
+        // Header words are followed by the bitmap, then sizes and offsets.
+        const SET_LEN_WORD: usize = 8;
+        let segment_count = fesia.segment_count();
+        let sizes = 10 + fesia.hash_size() / i32::BITS as usize;
+        let offsets = sizes + segment_count;
+        let segment = segment % segment_count;
+
+        let truncated = matches!(
+            load(&buffer[..cut % buffer.len()]),
+            Err(FesiaLoadError::Truncated { .. }));
+
+        let mut overflowing = buffer.clone();
+        overflowing[SET_LEN_WORD] = -1;
+        overflowing[SET_LEN_WORD + 1] = -1;
+        let overflow_rejected = load(&overflowing).is_err();
+
+        let mut out_of_range = buffer.clone();
+        out_of_range[offsets + segment] = i32::MAX;
+        let offset_rejected = load(&out_of_range) == Err(FesiaLoadError::Corrupt);
+
+        let mut negative = buffer.clone();
+        negative[sizes + segment] = -1;
+        let size_rejected = load(&negative) == Err(FesiaLoadError::Corrupt);
+
+        load(&buffer).is_ok() && truncated && overflow_rejected && offset_rejected && size_rejected
+    }
+
+    #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+    fn fesia_update_correct(
+        set: SortedSet<i32>,
+        other: SortedSet<i32>,
+        ops: Vec<(bool, i32)>,
+        dense: u16) -> bool
+    {
+        // Dense runs overflow segments and force a rehash. With an ordered hash
+        // they would all land in one segment, so only the random ops are used.
+        let inserts = (0..(dense % 2048) as i32).map(|item| (true, item));
+        let dense_ops: Vec<(bool, i32)> = ops.iter().copied().chain(inserts).collect();
+
+        [1.0, 16.0].into_iter().all(|scale|
+            fesia_update::<MixHash, i8, 16>(set.as_slice(), other.as_slice(), &dense_ops, scale) &&
+            fesia_update::<OrderedHash, i8, 16>(set.as_slice(), other.as_slice(), &ops, scale)
+        )
+    }
+
+    #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+    fn fesia_mixed_scale_correct(sets: SetCollection<i32>) -> bool {
+        let sets: Vec<SortedSet<i32>> = sets.into();
+        fesia_mixed_correct::<MixHash>(&sets) &&
+        fesia_mixed_correct::<OrderedHash>(&sets)
+    }
+
+    #[cfg(all(feature = "simd", target_feature = "sse4.2"))]
+    fn fesia_hash_families_correct(sets: SimilarSetPair<i32>, items: Vec<i32>) -> bool {
+        let set_a = sets.0.as_slice();
+        let set_b = sets.1.as_slice();
+        let correct = |method| {
+            fesia_correct::<Fesia<IdentityHash, i16, 8>>(set_a, set_b, 4.0, method, Sse) &&
+            fesia_correct::<Fesia<MultiplyShiftHash, i16, 8>>(set_a, set_b, 4.0, method, Sse) &&
+            fesia_correct::<Fesia<Crc32Hash, i16, 8>>(set_a, set_b, 4.0, method, Sse) &&
+            fesia_correct::<Fesia<TabulationHash, i16, 8>>(set_a, set_b, 4.0, method, Sse)
+        };
+
+        correct(SimilarSize) && correct(Skewed) &&
+        hash_batch_correct::<MixHash>(&items) &&
+        hash_batch_correct::<MultiplyShiftHash>(&items) &&
+        hash_batch_correct::<TabulationHash>(&items)
+    }
+
+    fn fesia_tune_correct(set: SortedSet<i32>) -> bool {
+        let set = set.as_slice();
+        let max_segment = SegmentIntersectSse::MAX_KERNEL;
+
+        let fesia = Fesia8Sse::with_max_segment(set, max_segment);
+        let stats = fesia.build_stats();
+        let tuning = tune_hash::<i8>(set, max_segment);
+
+        stats.max_segment_size <= max_segment &&
+        stats.segment_histogram.iter().sum::<usize>() == stats.segment_count &&
+        stats.segment_histogram.iter().enumerate().map(|(i, &n)| i * n).sum::<usize>() == set.len() &&
+        fesia.to_sorted_set() == set &&
+        tuning.hash_size <= fesia.hash_size()
+    }
+
+    #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+    fn fesia_union_difference_correct(sets: SimilarSetPair<i32>) -> bool {
+        let set_a = sets.0.as_slice();
+        let set_b = sets.1.as_slice();
+        [(4.0, 4.0), (1.0, 16.0), (16.0, 1.0)].into_iter().all(|(scale_a, scale_b)|
+            fesia_union_difference::<MixHash, i16, 8>(set_a, set_b, scale_a, scale_b) &&
+            fesia_union_difference::<OrderedHash, i8, 16>(set_a, set_b, scale_a, scale_b)
+        )
+    }
+
+    #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+    fn fesia_kset_methods_correct(sets: SetCollection<i32>) -> bool {
+        let sets: Vec<SortedSet<i32>> = sets.into();
+        let expected = intersect::run_svs(&sets, intersect::naive_merge);
+
+        // Mixed scales, with a tiny first set for the skewed method.
+        let fesia_sets: Vec<Fesia16Sse> = sets.iter().enumerate()
+            .map(|(i, s)| Fesia16Sse::from_sorted_scaled(s.as_slice(), (1 << (i % 3 * 2)) as f64))
+            .collect();
+        let finish = |visitor: VecWriter<i32>| {
+            let mut result: Vec<i32> = visitor.into();
+            result.sort();
+            result
+        };
+
+        let mut visitor: VecWriter<i32> = VecWriter::new();
+        Fesia16Sse::intersect_k_with::<SegmentBroadcastK<4>, _>(&fesia_sets, &mut visitor);
+        let simd4_correct = finish(visitor) == expected;
+
+        let mut visitor: VecWriter<i32> = VecWriter::new();
+        Fesia16Sse::intersect_k_with::<SegmentBroadcastK<16>, _>(&fesia_sets, &mut visitor);
+        let simd16_correct = finish(visitor) == expected;
+
+        let mut visitor: VecWriter<i32> = VecWriter::new();
+        Fesia16Sse::hash_intersect_k(&fesia_sets, &mut visitor);
+        let skewed_correct = finish(visitor) == expected;
+
+        simd4_correct && simd16_correct && skewed_correct
+    }
+
     fn merge_k_correct(sets: SetCollection<i32>) -> bool {
         let mut visitor: VecWriter<i32> = VecWriter::new();
         intersect::fesia::merge_k(sets.as_slice().iter().map(|s| s.as_slice()), &mut visitor);
@@ -724,9 +1392,108 @@ quickcheck! {
     // TODO: test FESIA k-set
     // then benchmark
 
+    // Prepared probes
+    fn bsr_probe_batch_correct(query: SortedSet<u32>, targets: SetCollection<u32>) -> bool {
+        let probe = BsrProbe::new(query.as_slice(), intersect::branchless_merge_bsr);
+        let target_bsrs: Vec<BsrVec> = targets.as_slice().iter()
+            .map(|t| BsrVec::from_sorted(t.as_slice()))
+            .collect();
+        let results = probe.probe_batch(
+            target_bsrs.iter().map(|t| t.bsr_ref()),
+            |_| BsrVec::new());
+
+        results.into_iter().zip(targets.as_slice()).all(|(result, target)|
+            prop_intersection_correct(
+                result.to_sorted_set(), &[query.as_slice(), target.as_slice()])
+        )
+    }
+
+    #[cfg(feature = "simd")]
+    fn broadcast_probe_batch_correct(query: SortedSet<i32>, targets: SetCollection<i32>) -> bool {
+        broadcast_probe_correct(BroadcastProbeSse::new(query.as_slice()), &targets) &&
+        broadcast_probe_correct(BroadcastProbeAvx2::new(query.as_slice()), &targets) &&
+        broadcast_probe_correct(BroadcastProbeAvx512::new(query.as_slice()), &targets)
+    }
+
+    #[cfg(feature = "simd")]
+    fn broadcast_probe_batch_unsigned_correct(query: SortedSet<u32>, targets: SetCollection<u32>) -> bool {
+        broadcast_probe_correct(BroadcastProbeAvx2::new(query.as_slice()), &targets)
+    }
+
+    #[cfg(feature = "simd")]
+    fn fesia_probe_batch_correct(query: SortedSet<i32>, targets: SetCollection<i32>) -> bool {
+        (0..5).map(|h| h as f64 * 2.0).all(|hash_scale| {
+            let probe = Fesia8Sse::from_sorted_scaled(query.as_slice(), hash_scale);
+            let results = probe.probe_batch(
+                targets.as_slice().iter().map(|t| t.as_slice()),
+                |_| VecWriter::new());
+
+            results.into_iter().zip(targets.as_slice()).all(|(result, target)|
+                prop_intersection_correct(result.into(), &[query.as_slice(), target.as_slice()])
+            )
+        })
+    }
+
+    // Graph
+    fn triangle_count_correct(edges: Vec<(u8, u8)>) -> bool {
+        let graph = small_graph(&edges);
+        let dag = graph.degree_ordered();
+        let expected = brute_force_cliques(&graph, 3);
+
+        graph::triangle_count(&dag, intersect::branchless_merge) == expected &&
+        graph::triangle_count_par(&dag, intersect::galloping, 3) == expected &&
+        graph::triangle_count_bsr(&dag, &dag.to_bsr(), intersect::branchless_merge_bsr) == expected
+    }
+
+    fn k_clique_count_correct(edges: Vec<(u8, u8)>) -> bool {
+        let graph = small_graph(&edges);
+        let dag = graph.degree_ordered();
+
+        (1..=4).all(|k| {
+            let expected = brute_force_cliques(&graph, k);
+            graph::k_clique_count(&dag, k, intersect::branchless_merge) == expected &&
+            graph::k_clique_count_par(&dag, k, intersect::galloping, 2) == expected
+        })
+    }
+
+    // Estimation
+    fn sample_estimate_correct(sets: SkewedSetPair<i32>) -> bool {
+        let small = sets.small.as_slice();
+        let large = sets.large.as_slice();
+        let exact = exact_count(small, large);
+
+        estimate::sample_estimate(small, large, small.len()) == Estimate::exact(exact) &&
+        within_error(estimate::sample_estimate(small, large, 32), exact)
+    }
+
+    fn kmv_estimate_correct(sets: SimilarSetPair<i32>) -> bool {
+        let set_a = sets.0.as_slice();
+        let set_b = sets.1.as_slice();
+        let exact = exact_count(set_a, set_b);
+
+        [8, 64, 1024].into_iter().all(|k| {
+            let sketch_a = KmvSketch::new(set_a, k);
+            let sketch_b = KmvSketch::new(set_b, k);
+            within_error(sketch_a.intersection(&sketch_b), exact)
+        })
+    }
+
+    #[cfg(feature = "simd")]
+    fn fesia_estimate_correct(sets: SimilarSetPair<i32>) -> bool {
+        let set_a = sets.0.as_slice();
+        let set_b = sets.1.as_slice();
+        let exact = exact_count(set_a, set_b);
+
+        [1.0, 4.0, 16.0].into_iter().all(|hash_scale| {
+            let fesia_a = Fesia8Sse::from_sorted_scaled(set_a, hash_scale);
+            let fesia_b = Fesia8Sse::from_sorted_scaled(set_b, hash_scale * 2.0);
+            within_error(fesia_a.estimate_intersection(&fesia_b), exact)
+        })
+    }
+
     // Misc
     fn bsr_encode_decode(set: SortedSet<u32>) -> bool {
-        set.as_ref() == BsrVec::from_sorted(set.as_ref()).to_sorted_set()
+        set.as_ref() == BsrVec::<u32>::from_sorted(set.as_ref()).to_sorted_set()
     }
 
     // Unsafe writer
@@ -758,6 +1525,137 @@ quickcheck! {
 
         actual == expected
     }
+
+    fn multiset_intersection_correct(pair: MultisetPair<i32>) -> bool {
+        let (set_a, set_b) = (pair.0.as_slice(), pair.1.as_slice());
+        multiset_intersect_kernels::<i32>().into_iter().all(|intersect| {
+            let forward = intersect::run_2set(set_a, set_b, intersect);
+            let backward = intersect::run_2set(set_b, set_a, intersect);
+            prop_multiset_intersection_correct(&forward, set_a, set_b) &&
+            prop_multiset_intersection_correct(&backward, set_a, set_b)
+        })
+    }
+
+    fn bag_union_correct(pair: MultisetPair<i32>) -> bool {
+        let (set_a, set_b) = (pair.0.as_slice(), pair.1.as_slice());
+        bag_union_kernels::<i32>().into_iter().all(|union| {
+            let forward = intersect::run_2set(set_a, set_b, union);
+            let backward = intersect::run_2set(set_b, set_a, union);
+            prop_bag_union_correct(&forward, set_a, set_b) &&
+            prop_bag_union_correct(&backward, set_a, set_b)
+        })
+    }
+
+    fn multiset_unsigned_correct(pair: MultisetPair<u32>) -> bool {
+        let (set_a, set_b) = (pair.0.as_slice(), pair.1.as_slice());
+        multiset_intersect_kernels::<u32>().into_iter().all(|intersect|
+            prop_multiset_intersection_correct(
+                &intersect::run_2set(set_a, set_b, intersect), set_a, set_b)
+        ) &&
+        bag_union_kernels::<u32>().into_iter().all(|union|
+            prop_bag_union_correct(
+                &intersect::run_2set(set_a, set_b, union), set_a, set_b)
+        )
+    }
+
+    fn keyed_intersection_correct(similar: SimilarSetPair<u32>, skewed: SkewedSetPair<u32>) -> bool {
+        [
+            (similar.0.as_slice(), similar.1.as_slice()),
+            (skewed.small.as_slice(), skewed.large.as_slice()),
+        ].into_iter().all(|(ids_a, ids_b)| {
+            let set_a = to_records(ids_a, 1.0);
+            let set_b = to_records(ids_b, -1.0);
+            let key = |record: &Record| record.id;
+
+            let expected: Vec<(Record, Record)> =
+                intersect::run_2set(ids_a, ids_b, intersect::naive_merge)
+                .into_iter()
+                .map(|id| (record(id, 1.0), record(id, -1.0)))
+                .collect();
+
+            let mut merged = VecWriter::new();
+            intersect::merge_by_key(&set_a, &set_b, key, &mut merged);
+            let mut galloped = VecWriter::new();
+            intersect::galloping_by_key(&set_a, &set_b, key, &mut galloped);
+
+            let mut left = Map::new(VecWriter::new(), |(a, _): (Record, Record)| a);
+            intersect::merge_by_key(&set_a, &set_b, key, &mut left);
+            let left: Vec<Record> = left.into_inner().into();
+
+            // Buffers are shared across kernels to check they are reset.
+            let mut buffers = intersect::KeyBuffers::new();
+
+            Vec::from(merged) == expected &&
+            Vec::from(galloped) == expected &&
+            left.iter().copied().eq(expected.iter().map(|&(a, _)| a)) &&
+            unsigned_kernels().into_iter().all(|kernel| {
+                let mut writer = VecWriter::new();
+                intersect::intersect_by_key(&set_a, &set_b, key, kernel, &mut writer);
+                let mut reused = VecWriter::new();
+                intersect::intersect_by_key_with(
+                    &set_a, &set_b, key, kernel, &mut buffers, &mut reused);
+                Vec::from(writer) == expected && Vec::from(reused) == expected
+            })
+        })
+    }
+}
+
+fn exact_count(set_a: &[i32], set_b: &[i32]) -> usize {
+    let mut counter = Counter::new();
+    intersect::branchless_merge(set_a, set_b, &mut counter);
+    counter.count()
+}
+
+fn within_error(estimate: Estimate, exact: usize) -> bool {
+    (estimate.value - exact as f64).abs() <= 6.0 * estimate.std_error + 1.0
+}
+
+// Copies the persisted set into an i32 buffer so that it is suitably aligned.
+#[cfg(feature = "simd")]
+fn persist_aligned<H, S, const LANES: usize>(set: &Fesia<H, S, LANES>) -> Vec<i32>
+where
+    H: IntegerHash,
+    S: SimdElement + MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+    Simd<S, LANES>: BitAnd<Output=Simd<S, LANES>> + SimdPartialEq<Mask=Mask<S, LANES>>,
+{
+    let mut bytes = Vec::new();
+    set.to_writer(&mut bytes).unwrap();
+    assert!(bytes.len() % 4 == 0);
+    bytes.chunks_exact(4)
+        .map(|word| i32::from_ne_bytes(word.try_into().unwrap()))
+        .collect()
+}
+
+#[cfg(feature = "simd")]
+fn aligned_bytes(buffer: &[i32]) -> &[u8] {
+    unsafe {
+        std::slice::from_raw_parts(buffer.as_ptr() as *const u8, std::mem::size_of_val(buffer))
+    }
+}
+
+// Vertex ids are reduced so that small graphs are dense enough to have cliques.
+fn small_graph(edges: &[(u8, u8)]) -> CsrGraph {
+    CsrGraph::from_edges(edges.iter().map(|&(a, b)| ((a % 32) as u32, (b % 32) as u32)))
+}
+
+fn brute_force_cliques(graph: &CsrGraph, k: usize) -> usize {
+    fn extend(graph: &CsrGraph, k: usize, clique: &mut Vec<u32>) -> usize {
+        if clique.len() == k {
+            return 1;
+        }
+        let start = clique.last().map(|&v| v + 1).unwrap_or(0);
+        let mut count = 0;
+        for v in start..graph.vertex_count() as u32 {
+            if clique.iter().all(|&u| graph.neighbours(u).binary_search(&v).is_ok()) {
+                clique.push(v);
+                count += extend(graph, k, clique);
+                clique.pop();
+            }
+        }
+        count
+    }
+    extend(graph, k, &mut Vec::new())
 }
 
 fn run_unsafe_writer<T>(
@@ -783,8 +1681,8 @@ where
     let expected = intersect::run_2set(
         set_a, set_b, intersect::naive_merge);
 
-    let set1 = S::from_sorted(set_a, hash_scale);
-    let set2 = S::from_sorted(set_b, hash_scale);
+    let set1 = S::from_sorted_scaled(set_a, hash_scale);
+    let set2 = S::from_sorted_scaled(set_b, hash_scale);
     let mut visitor: VecWriter<i32> = VecWriter::new();
 
     match (intersect_method, simd_type) {
@@ -812,7 +1710,441 @@ where
     actual == expected
 }
 
+fn set_traits<T, S>(set_a: &[T], set_b: &[T], expected: &[T]) -> bool
+where
+    T: Clone + PartialEq + std::fmt::Debug,
+    S: Set<T> + setops::Intersect + Cardinality + ToSorted<T>,
+{
+    let left = S::from_sorted(set_a);
+    let right = S::from_sorted(set_b);
+    let result = left.intersect(&right);
+
+    left.to_sorted() == set_a &&
+    left.cardinality() == set_a.len() &&
+    left.intersect_count(&right) == expected.len() &&
+    result.cardinality() == expected.len() &&
+    result.to_sorted() == expected
+}
+
+fn bsr_ops(left: &BsrVec, right: &BsrVec, kernel: bsr::MatchStates) -> bool {
+    let set_a: BTreeSet<u32> = left.to_sorted_set().into_iter().collect();
+    let set_b: BTreeSet<u32> = right.to_sorted_set().into_iter().collect();
+    let (a, b) = (left.bsr_ref(), right.bsr_ref());
+
+    a.union_with(b, kernel).to_sorted_set() ==
+        set_a.union(&set_b).copied().collect::<Vec<_>>() &&
+    a.difference_with(b, kernel).to_sorted_set() ==
+        set_a.difference(&set_b).copied().collect::<Vec<_>>() &&
+    a.symmetric_difference_with(b, kernel).to_sorted_set() ==
+        set_a.symmetric_difference(&set_b).copied().collect::<Vec<_>>()
+}
+
 #[cfg(feature = "simd")]
+fn fesia_kernels<H: IntegerHash>(set_a: &[i32], set_b: &[i32], hash_scale: HashScale) -> bool {
+    type F<H> = Fesia<H, i8, 16>;
+    let expected = intersect::run_2set(set_a, set_b, intersect::naive_merge);
+    let fesia_a = F::<H>::from_sorted_scaled(set_a, hash_scale);
+    let fesia_b = F::<H>::from_sorted_scaled(set_b, hash_scale);
+
+    let run = |intersect: fn(&F<H>, &F<H>, &mut VecWriter<i32>)| {
+        let mut visitor: VecWriter<i32> = VecWriter::new();
+        intersect(&fesia_a, &fesia_b, &mut visitor);
+        let mut result: Vec<i32> = visitor.into();
+        result.sort();
+        result == expected
+    };
+
+    run(|a, b, v| a.intersect::<_, SegmentIntersectSimd<2, 2>>(b, v)) &&
+    run(|a, b, v| a.intersect::<_, SegmentIntersectSimd<4, 1>>(b, v)) &&
+    run(|a, b, v| a.intersect::<_, SegmentIntersectSimd<8, 4>>(b, v)) &&
+    run(|a, b, v| a.intersect::<_, SegmentIntersectSimd<16, 2>>(b, v))
+}
+
+fn inplace_kernels<T>() -> Vec<intersect::Intersect2InPlace<T>>
+where
+    T: SimdElement + Ord,
+    Simd<T, 4>: SimdPartialEq<Mask=Mask<T::Mask, 4>>,
+    Simd<T, 8>: SimdPartialEq<Mask=Mask<T::Mask, 8>>,
+    Simd<T, 16>: SimdPartialEq<Mask=Mask<T::Mask, 16>>,
+{
+    vec![
+        intersect::galloping_inplace,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::shuffling_sse_inplace,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::broadcast_sse_inplace,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::galloping_sse_inplace,
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        intersect::shuffling_avx2_inplace,
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        intersect::broadcast_avx2_inplace,
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        intersect::galloping_avx2_inplace,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::shuffling_avx512_inplace,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::broadcast_avx512_inplace,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::galloping_avx512_inplace,
+    ]
+}
+
+// Every 2-set kernel which is generic over its items.
+fn unsigned_kernels() -> Vec<Intersect2<[u32], VecWriter<u32>>> {
+    vec![
+        intersect::naive_merge,
+        intersect::branchless_merge,
+        intersect::bmiss_scalar_3x,
+        intersect::bmiss_scalar_4x,
+        intersect::galloping,
+        intersect::binary_search_intersect,
+        intersect::baezayates,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::shuffling_sse,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::shuffling_sse_branch,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::broadcast_sse,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::broadcast_sse_branch,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::galloping_sse,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::lbk_v1x4_sse,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::lbk_v1x8_sse,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::lbk_v3_sse,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::bmiss,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::bmiss_branch,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::bmiss_sttni,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::bmiss_sttni_branch,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::qfilter,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::qfilter_branch,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::qfilter_v1,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::qfilter_v1_branch,
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        intersect::shuffling_avx2,
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        intersect::shuffling_avx2_branch,
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        intersect::broadcast_avx2,
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        intersect::broadcast_avx2_branch,
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        intersect::galloping_avx2,
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        intersect::lbk_v1x8_avx2,
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        intersect::lbk_v1x16_avx2,
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        intersect::lbk_v3_avx2,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::shuffling_avx512,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::shuffling_avx512_branch,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::broadcast_avx512,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::broadcast_avx512_branch,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::galloping_avx512,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::lbk_v1x16_avx512,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::lbk_v1x32_avx512,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::lbk_v3_avx512,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::vp2intersect_emulation,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::vp2intersect_emulation_branch,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::conflict_intersect,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::conflict_intersect_branch,
+        intersect::mono::naive_merge_mono_u32,
+        intersect::mono::branchless_merge_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::mono::shuffling_sse_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::mono::shuffling_sse_branch_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        intersect::mono::shuffling_avx2_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        intersect::mono::shuffling_avx2_branch_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::mono::shuffling_avx512_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::mono::shuffling_avx512_branch_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::mono::broadcast_sse_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::mono::broadcast_sse_branch_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        intersect::mono::broadcast_avx2_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "avx2"))]
+        intersect::mono::broadcast_avx2_branch_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::mono::broadcast_avx512_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::mono::broadcast_avx512_branch_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::mono::bmiss_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::mono::bmiss_branch_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::mono::bmiss_sttni_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::mono::bmiss_sttni_branch_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::mono::qfilter_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "ssse3"))]
+        intersect::mono::qfilter_branch_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::mono::vp2intersect_emulation_mono_u32,
+        #[cfg(all(feature = "simd", target_feature = "avx512f"))]
+        intersect::mono::vp2intersect_emulation_branch_mono_u32,
+    ]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Record {
+    id: u32,
+    score: f32,
+}
+
+fn record(id: u32, sign: f32) -> Record {
+    Record { id, score: sign * id as f32 }
+}
+
+fn to_records(ids: &[u32], sign: f32) -> Vec<Record> {
+    ids.iter().map(|&id| record(id, sign)).collect()
+}
+
+fn multiset_intersect_kernels<T>() -> Vec<Intersect2<[T], VecWriter<T>>>
+where
+    T: SimdElement<Mask = i32> + Ord,
+    Simd<T, 4>: SimdPartialOrd<Mask=Mask<i32, 4>>,
+    Simd<T, 8>: SimdPartialOrd<Mask=Mask<i32, 8>>,
+    Simd<T, 16>: SimdPartialOrd<Mask=Mask<i32, 16>>,
+{
+    vec![
+        intersect::naive_merge,
+        intersect::branchless_merge,
+        intersect::multiset_galloping,
+        #[cfg(feature = "simd")]
+        intersect::multiset_galloping_sse,
+        #[cfg(feature = "simd")]
+        intersect::multiset_galloping_avx2,
+        #[cfg(feature = "simd")]
+        intersect::multiset_galloping_avx512,
+        #[cfg(feature = "simd")]
+        intersect::multiset_broadcast_sse,
+        #[cfg(feature = "simd")]
+        intersect::multiset_broadcast_avx2,
+        #[cfg(feature = "simd")]
+        intersect::multiset_broadcast_avx512,
+    ]
+}
+
+fn bag_union_kernels<T>() -> Vec<Intersect2<[T], VecWriter<T>>>
+where
+    T: SimdElement + Ord,
+    Simd<T, 4>: SimdPartialEq<Mask=Mask<T::Mask, 4>>,
+    Simd<T, 8>: SimdPartialEq<Mask=Mask<T::Mask, 8>>,
+    Simd<T, 16>: SimdPartialEq<Mask=Mask<T::Mask, 16>>,
+{
+    vec![
+        intersect::bag_union_merge,
+        intersect::bag_union_galloping,
+        #[cfg(feature = "simd")]
+        intersect::bag_union_galloping_sse,
+        #[cfg(feature = "simd")]
+        intersect::bag_union_galloping_avx2,
+        #[cfg(feature = "simd")]
+        intersect::bag_union_galloping_avx512,
+    ]
+}
+
+// The probe is built once and reused for every target.
+#[cfg(feature = "simd")]
+fn broadcast_probe_correct<T, const LANES: usize>(
+    probe: BroadcastProbe<T, LANES>,
+    targets: &SetCollection<T>) -> bool
+where
+    T: Ord + Copy + quickcheck::Arbitrary,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    let results = probe.probe_batch(
+        targets.as_slice().iter().map(|t| t.as_slice()),
+        |_| VecWriter::new());
+
+    results.into_iter().zip(targets.as_slice()).all(|(result, target)|
+        prop_intersection_correct(result.into(), &[probe.set(), target.as_slice()])
+    )
+}
+
+fn run_mixed<T, L>(small: &[T], large: &L, intersect: fn(&[T], &L, &mut VecWriter<T>)) -> Vec<T> {
+    let mut writer = VecWriter::new();
+    intersect(small, large, &mut writer);
+    writer.into()
+}
+
+// Builds each set with a different scale. Results must be sorted for ordered
+// hashes.
+#[cfg(all(feature = "simd", target_feature = "ssse3"))]
+fn fesia_mixed_correct<H: IntegerHash>(sets: &[SortedSet<i32>]) -> bool {
+    const SCALES: [HashScale; 4] = [1.0, 16.0, 64.0, 4.0];
+
+    let fesia_sets: Vec<Fesia<H, i16, 8>> = sets.iter().enumerate()
+        .map(|(i, s)| Fesia::from_sorted_scaled(s.as_slice(), SCALES[i % SCALES.len()]))
+        .collect();
+    let finish = |visitor: VecWriter<i32>| {
+        let mut result: Vec<i32> = visitor.into();
+        if !H::ORDERED {
+            result.sort();
+        }
+        result
+    };
+
+    let expected = intersect::run_2set(
+        sets[0].as_slice(), sets[1].as_slice(), intersect::naive_merge);
+
+    let mut visitor: VecWriter<i32> = VecWriter::new();
+    fesia_sets[0].intersect::<VecWriter<i32>, SegmentIntersectSse>(&fesia_sets[1], &mut visitor);
+    let twoset_correct = finish(visitor) == expected;
+
+    let mut visitor: VecWriter<i32> = VecWriter::new();
+    fesia_sets[1].hash_intersect(&fesia_sets[0], &mut visitor);
+    let hash_correct = finish(visitor) == expected;
+
+    let mut visitor: VecWriter<i32> = VecWriter::new();
+    Fesia::intersect_k(&fesia_sets, &mut visitor);
+    let kset_correct = finish(visitor) == intersect::run_svs(sets, intersect::naive_merge);
+
+    twoset_correct && hash_correct && kset_correct &&
+    fesia_sets.iter().zip(sets).all(|(f, s)| f.to_sorted_set() == s.as_slice())
+}
+
+#[cfg(feature = "simd")]
+fn hash_batch_correct<H: IntegerHash>(items: &[i32]) -> bool {
+    let mut hashes = vec![0; items.len()];
+    H::hash_batch(items, &mut hashes);
+    items.iter().zip(hashes).all(|(&item, hash)| H::hash(item) == hash)
+}
+
+// Results must match a fresh build with the same hash size byte for byte.
+#[cfg(all(feature = "simd", target_feature = "ssse3"))]
+// Updates leave slack between segments, which every intersection method must
+// skip, including against sets of another scale.
+fn fesia_update<H, S, const LANES: usize>(
+    set: &[i32],
+    other: &[i32],
+    ops: &[(bool, i32)],
+    scale_other: HashScale) -> bool
+where
+    H: IntegerHash,
+    S: SimdElement + MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+    Simd<S, LANES>: BitAnd<Output=Simd<S, LANES>> + SimdPartialEq<Mask=Mask<S, LANES>>,
+{
+    let mut expected: BTreeSet<i32> = set.iter().copied().collect();
+    let mut fesia: Fesia<H, S, LANES> = Fesia::from_sorted_scaled(set, 1.0);
+
+    let ops_same = ops.iter().all(|&(insert, item)|
+        if insert {
+            fesia.insert::<SegmentIntersectSse>(item) == expected.insert(item)
+        }
+        else {
+            fesia.remove(item) == expected.remove(&item)
+        });
+
+    let sorted: Vec<i32> = expected.into_iter().collect();
+    let other_fesia: Fesia<H, S, LANES> = Fesia::from_sorted_scaled(other, scale_other);
+    let expected_intersection = intersect::run_svs(&[sorted.as_slice(), other], intersect::naive_merge);
+    let finish = |visitor: VecWriter<i32>| {
+        let mut result: Vec<i32> = visitor.into();
+        result.sort();
+        result
+    };
+
+    let mut visitor: VecWriter<i32> = VecWriter::new();
+    fesia.intersect::<VecWriter<i32>, SegmentIntersectSse>(&other_fesia, &mut visitor);
+    let twoset_same = finish(visitor) == expected_intersection;
+
+    let mut visitor: VecWriter<i32> = VecWriter::new();
+    other_fesia.intersect::<VecWriter<i32>, SegmentIntersectSse>(&fesia, &mut visitor);
+    let reversed_same = finish(visitor) == expected_intersection;
+
+    let mut visitor: VecWriter<i32> = VecWriter::new();
+    fesia.hash_intersect(&other_fesia, &mut visitor);
+    let hash_same = finish(visitor) == expected_intersection;
+
+    let mut visitor: VecWriter<i32> = VecWriter::new();
+    Fesia::<H, S, LANES>::intersect_k(&[&fesia, &other_fesia, &fesia], &mut visitor);
+    let kset_same = finish(visitor) == expected_intersection;
+
+    let buffer = persist_aligned(&fesia);
+    let loaded = FesiaRef::<H, S, LANES>::from_bytes(aligned_bytes(&buffer)).unwrap();
+
+    ops_same && twoset_same && reversed_same && hash_same && kset_same &&
+    fesia.to_sorted_set() == sorted &&
+    fesia.cardinality() == sorted.len() &&
+    loaded.to_sorted_set() == sorted &&
+    sorted.iter().all(|&item| fesia.contains(item))
+}
+
+fn fesia_union_difference<H, S, const LANES: usize>(
+    set_a: &[i32],
+    set_b: &[i32],
+    scale_a: HashScale,
+    scale_b: HashScale) -> bool
+where
+    H: IntegerHash,
+    S: SimdElement + MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+    Simd<S, LANES>: BitAnd<Output=Simd<S, LANES>> + SimdPartialEq<Mask=Mask<S, LANES>>,
+{
+    let fesia_a: Fesia<H, S, LANES> = Fesia::from_sorted_scaled(set_a, scale_a);
+    let fesia_b: Fesia<H, S, LANES> = Fesia::from_sorted_scaled(set_b, scale_b);
+
+    let mut expected_union: Vec<i32> = set_a.iter().chain(set_b).copied().collect();
+    expected_union.sort();
+    expected_union.dedup();
+    let expected_difference: Vec<i32> = set_a.iter().copied()
+        .filter(|item| set_b.binary_search(item).is_err())
+        .collect();
+
+    let same_as_fresh = |fesia: &Fesia<H, S, LANES>, sorted: &[i32]| {
+        let fresh: Fesia<H, S, LANES> = Fesia::with_hash_size(sorted, fesia.hash_size());
+        let mut actual = Vec::new();
+        let mut expected = Vec::new();
+        fesia.to_writer(&mut actual).unwrap();
+        fresh.to_writer(&mut expected).unwrap();
+        actual == expected
+    };
+
+    let union = fesia_a.union(&fesia_b);
+    let difference = fesia_a.difference::<SegmentIntersectSse>(&fesia_b);
+
+    fesia_a.union_sorted(&fesia_b) == expected_union &&
+    fesia_a.difference_sorted::<SegmentIntersectSse>(&fesia_b) == expected_difference &&
+    union.hash_size() == fesia_a.hash_size().max(fesia_b.hash_size()) &&
+    difference.hash_size() == fesia_a.hash_size() &&
+    same_as_fresh(&union, &expected_union) &&
+    same_as_fresh(&difference, &expected_difference)
+}
+
 fn fesia_kset_correct<S>(
     sets: &[SortedSet<i32>],
     hash_scale: HashScale) -> bool
@@ -822,7 +2154,7 @@ where
 
     let expected = intersect::run_svs(sets, intersect::naive_merge);
 
-    let fesia_sets: Vec<S> = sets.iter().map(|s| S::from_sorted(s.as_slice(), hash_scale)).collect();
+    let fesia_sets: Vec<S> = sets.iter().map(|s| S::from_sorted_scaled(s.as_slice(), hash_scale)).collect();
 
     let mut visitor: VecWriter<i32> = VecWriter::new();
 
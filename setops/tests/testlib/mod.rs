@@ -68,6 +68,58 @@ where
     }
 }
 
+// Arbitrary Multiset //
+// Non-decreasing, i.e., sorted but with duplicates kept.
+#[derive(Debug, Clone)]
+pub struct SortedMultiset<T>(Vec<T>)
+where
+    T: Ord + Arbitrary + Copy;
+
+impl<T> SortedMultiset<T>
+where
+    T: Ord + Arbitrary + Copy
+{
+    pub fn from_unsorted(mut vec: Vec<T>) -> Self {
+        vec.sort_unstable();
+        Self(vec)
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> quickcheck::Arbitrary for SortedMultiset<T>
+where
+    T: Ord + Arbitrary + Copy
+{
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        Self::from_unsorted(vec_with_duplicates(Vec::arbitrary(g), g))
+    }
+}
+
+impl<T> AsRef<[T]> for SortedMultiset<T>
+where
+    T: Ord + Arbitrary + Copy
+{
+    fn as_ref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+// Repeats each item between 1 and 20 times so runs span whole vectors.
+fn vec_with_duplicates<T>(items: Vec<T>, g: &mut quickcheck::Gen) -> Vec<T>
+where
+    T: Copy
+{
+    let mut result = Vec::with_capacity(items.len());
+    for item in items {
+        let count = usize::arbitrary(g) % 20 + 1;
+        result.extend(std::iter::repeat_n(item, count));
+    }
+    result
+}
+
 // Arbitrary Intersection Function //
 #[derive(Clone)]
 pub struct DualIntersectFn(
@@ -152,6 +204,31 @@ where
     }
 }
 
+// Pair of multisets sharing some items, each with independent multiplicities.
+#[derive(Debug, Clone)]
+pub struct MultisetPair<T>(pub SortedMultiset<T>, pub SortedMultiset<T>)
+where
+    T: Ord + Arbitrary + Copy;
+
+impl<T> quickcheck::Arbitrary for MultisetPair<T>
+where
+    T: Ord + Arbitrary + Copy
+{
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        let shared: Vec<T> = Vec::arbitrary(g);
+
+        let mut left = Vec::arbitrary(g);
+        let mut right = Vec::arbitrary(g);
+        left.extend(&shared);
+        right.extend(&shared);
+
+        MultisetPair(
+            SortedMultiset::from_unsorted(vec_with_duplicates(left, g)),
+            SortedMultiset::from_unsorted(vec_with_duplicates(right, g)),
+        )
+    }
+}
+
 fn vec_of_len<T: Arbitrary>(len: usize, g: &mut quickcheck::Gen) -> Vec<T> {
     let mut result: Vec<T> = Vec::with_capacity(len);
     while result.len() < len {
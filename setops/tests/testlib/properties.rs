@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 
 pub fn prop_intersection_correct<S, T>(result: Vec<T>, sets: &[S]) -> bool
 where
@@ -48,3 +50,51 @@ where
     }
     true
 }
+
+// Each item occurs as many times as in the input it occurs least in.
+pub fn prop_multiset_intersection_correct<T>(result: &[T], set_a: &[T], set_b: &[T]) -> bool
+where
+    T: Ord + Copy,
+{
+    prop_non_decreasing(result) &&
+    multiset_counts(result) == combine_counts(set_a, set_b, usize::min)
+}
+
+// Each item occurs as many times as in the input it occurs most in.
+pub fn prop_bag_union_correct<T>(result: &[T], set_a: &[T], set_b: &[T]) -> bool
+where
+    T: Ord + Copy,
+{
+    prop_non_decreasing(result) &&
+    multiset_counts(result) == combine_counts(set_a, set_b, usize::max)
+}
+
+pub fn prop_non_decreasing<T>(result: &[T]) -> bool
+where
+    T: Ord + Copy,
+{
+    result.windows(2).all(|w| w[0] <= w[1])
+}
+
+fn multiset_counts<T: Ord + Copy>(items: &[T]) -> BTreeMap<T, usize> {
+    let mut counts = BTreeMap::new();
+    for &item in items {
+        *counts.entry(item).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn combine_counts<T, F>(set_a: &[T], set_b: &[T], combine: F) -> BTreeMap<T, usize>
+where
+    T: Ord + Copy,
+    F: Fn(usize, usize) -> usize,
+{
+    let counts_a = multiset_counts(set_a);
+    let counts_b = multiset_counts(set_b);
+    counts_a.keys().chain(counts_b.keys())
+        .map(|&item| (item, combine(
+            counts_a.get(&item).copied().unwrap_or(0),
+            counts_b.get(&item).copied().unwrap_or(0))))
+        .filter(|&(_, count)| count > 0)
+        .collect()
+}
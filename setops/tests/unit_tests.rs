@@ -1,4 +1,4 @@
-use setops::{visitor::VecWriter, intersect};
+use setops::{visitor::VecWriter, intersect, graph::CsrGraph};
 
 
 // Sanity check
@@ -57,3 +57,16 @@ fn test_simd_galloping() {
 
     assert!(actual == expected);
 }
+
+#[test]
+fn test_edge_list() {
+    let input = "# comment\n0 1\n1\t0\n1 2\n\n2 2\n0 2\n";
+    let graph = CsrGraph::from_edge_list(input.as_bytes()).unwrap();
+
+    assert_eq!(graph.vertex_count(), 3);
+    assert_eq!(graph.neighbours(0), &[1, 2]);
+    assert_eq!(graph.neighbours(1), &[0, 2]);
+    assert_eq!(graph.neighbours(2), &[0, 1]);
+
+    assert!(CsrGraph::from_edge_list("0 x\n".as_bytes()).is_err());
+}